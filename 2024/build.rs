@@ -0,0 +1,59 @@
+// Generates a test for every part of every example input with expected answers. Examples are
+// `data/dayN_<name>.txt` files, and their answers go in a `data/dayN_<name>.expected` sidecar
+// with a `part1 = ...` and/or `part2 = ...` line. The tests themselves are in tests/examples.rs.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+const DATA_DIR: &str = "data";
+
+fn main() {
+    println!("cargo:rerun-if-changed={DATA_DIR}");
+
+    let mut sidecars: Vec<_> = fs::read_dir(DATA_DIR)
+        .expect("Unable to read the data directory")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "expected"))
+        .collect();
+    sidecars.sort();
+
+    let mut tests = String::new();
+    for sidecar in sidecars {
+        let name = sidecar.file_stem().unwrap().to_str().unwrap();
+        let day: u8 = name
+            .strip_prefix("day")
+            .and_then(|rest| rest.split('_').next())
+            .and_then(|day| day.parse().ok())
+            .unwrap_or_else(|| panic!("{} isn't named like dayN_name", sidecar.display()));
+        let input = format!("{name}.txt");
+        if !Path::new(DATA_DIR).join(&input).exists() {
+            panic!("{} has no {input} to go with it", sidecar.display());
+        }
+
+        let expected: toml::Table = fs::read_to_string(&sidecar)
+            .unwrap()
+            .parse()
+            .unwrap_or_else(|e| panic!("Unable to parse {}: {e}", sidecar.display()));
+        for (key, value) in expected {
+            let part = match key.as_str() {
+                "part1" => "One",
+                "part2" => "Two",
+                _ => panic!("Unknown key {key:?} in {}", sidecar.display()),
+            };
+            let answer = match value {
+                toml::Value::String(s) => s,
+                other => other.to_string(),
+            };
+            writeln!(
+                tests,
+                "#[test]\nfn {name}_{key}() {{\n    check({day}, {input:?}, Part::{part}, {answer:?});\n}}\n"
+            )
+            .unwrap();
+        }
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out, tests).unwrap();
+}
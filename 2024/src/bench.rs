@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AocError, Part, Solver};
+
+/// Where benchmark results get saved, relative to the package root
+pub const DEFAULT_BASELINE: &str = "bench.json";
+
+/// The separately timed pieces of solving a day
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl From<Part> for Stage {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => Stage::Part1,
+            Part::Two => Stage::Part2,
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part 1",
+            Stage::Part2 => "part 2",
+        })
+    }
+}
+
+/// Summary of a bunch of timed runs. Times are kept in nanoseconds so they serialize nicely.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+impl Stats {
+    /// Summarize some samples. Returns None if there aren't any.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut ns: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+        ns.sort_unstable();
+
+        // Nearest-rank percentile, so p95 of a handful of runs is just the slowest one
+        let p95 = (ns.len() * 95).div_ceil(100).max(1) - 1;
+        Some(Stats {
+            runs: ns.len(),
+            min_ns: ns[0],
+            median_ns: ns[ns.len() / 2],
+            p95_ns: ns[p95],
+        })
+    }
+
+    pub fn min(&self) -> Duration {
+        Duration::from_nanos(self.min_ns)
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+
+    pub fn p95(&self) -> Duration {
+        Duration::from_nanos(self.p95_ns)
+    }
+
+    /// How much slower (positive) or faster (negative) this is than `baseline`, as a fraction of
+    /// the baseline's median
+    pub fn change_from(&self, baseline: &Stats) -> f64 {
+        if baseline.median_ns == 0 {
+            return 0.0;
+        }
+        (self.median_ns as f64 - baseline.median_ns as f64) / baseline.median_ns as f64
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "min {:>9.1?}  median {:>9.1?}  p95 {:>9.1?}",
+            self.min(),
+            self.median(),
+            self.p95()
+        )
+    }
+}
+
+/// How many times to run things
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    // Untimed runs to warm up caches and the like
+    pub warmup: usize,
+    pub runs: usize,
+}
+
+/// Time a function, after some warmup runs
+pub fn time<T>(config: &Config, mut f: impl FnMut() -> T) -> Stats {
+    for _ in 0..config.warmup {
+        black_box(f());
+    }
+
+    let samples: Vec<Duration> = (0..config.runs.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+
+    Stats::from_samples(&samples).unwrap()
+}
+
+/// Time parsing a day's input and then each of the requested parts. Parts get run against a single
+/// parsed copy of the input, so their times don't include parsing.
+pub fn bench_day(
+    solver: &dyn Solver,
+    input: &str,
+    parts: &[Part],
+    config: &Config,
+) -> Result<Vec<(Stage, Stats)>, AocError> {
+    // Parse once up front so that bad input is an error rather than a panic mid-benchmark
+    let parsed = solver.parse(input)?;
+
+    let mut results = vec![(Stage::Parse, time(config, || solver.parse(input)))];
+    for &part in parts {
+        let stats = time(config, || solver.solve(parsed.as_ref(), part));
+        results.push((part.into(), stats));
+    }
+    Ok(results)
+}
+
+/// Saved results to compare later runs against, by day and stage
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub days: BTreeMap<u8, BTreeMap<Stage, Stats>>,
+}
+
+impl Baseline {
+    /// Load a baseline, or an empty one if the file doesn't exist yet
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => serde_json::from_str(&s).map_err(io::Error::other),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Baseline::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json + "\n")
+    }
+
+    pub fn get(&self, day: u8, stage: Stage) -> Option<&Stats> {
+        self.days.get(&day).and_then(|stages| stages.get(&stage))
+    }
+
+    pub fn set(&mut self, day: u8, stage: Stage, stats: Stats) {
+        self.days.entry(day).or_default().insert(stage, stats);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: &[u64]) -> Vec<Duration> {
+        n.iter().map(|&n| Duration::from_millis(n)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<u64> = (1..=20).rev().collect();
+        let stats = Stats::from_samples(&ms(&samples)).unwrap();
+        assert_eq!(stats.runs, 20);
+        assert_eq!(stats.min(), Duration::from_millis(1));
+        assert_eq!(stats.median(), Duration::from_millis(11));
+        assert_eq!(stats.p95(), Duration::from_millis(19));
+
+        let stats = Stats::from_samples(&ms(&[5, 3])).unwrap();
+        assert_eq!(stats.p95(), Duration::from_millis(5));
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_change_from() {
+        let base = Stats::from_samples(&ms(&[100])).unwrap();
+        let slower = Stats::from_samples(&ms(&[125])).unwrap();
+        assert_eq!(slower.change_from(&base), 0.25);
+        assert_eq!(base.change_from(&slower), -0.2);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        let stats = Stats::from_samples(&ms(&[1, 2, 3])).unwrap();
+        baseline.set(6, Stage::Part2, stats);
+
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert!(json.contains(r#""6":{"part2":"#), "{json}");
+        let loaded: Baseline = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.get(6, Stage::Part2), Some(&stats));
+        assert_eq!(loaded.get(6, Stage::Parse), None);
+    }
+}
@@ -0,0 +1,409 @@
+use std::env;
+use std::error::Error;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use log::LevelFilter;
+
+use aoc2024::bench::{self, Baseline};
+use aoc2024::days::day6;
+use aoc2024::fetch::{self, Fetched};
+use aoc2024::input::{self, Source};
+use aoc2024::ledger::{self, Check, Ledger};
+use aoc2024::progress::{self, Mode};
+use aoc2024::render::{Format, Frames, Renderer};
+use aoc2024::{days, parallel, scaffold, Part, Solution, Solver};
+
+// Where the source lives, so that files which belong next to it end up there no matter where
+// we're run from
+const PACKAGE_ROOT: &str = env!("CARGO_MANIFEST_DIR");
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2024 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Threads to use for solutions which run in parallel, when built with the `parallel` feature
+    #[arg(long, global = true)]
+    threads: Option<usize>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solution for a day, or all of them
+    Run {
+        /// Day to run
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u8>,
+
+        /// Only run this part (1 or 2)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Run every day
+        #[arg(long)]
+        all: bool,
+
+        /// Read input from this file instead, or `-` for stdin
+        #[arg(long, conflicts_with_all = ["all", "example"])]
+        input: Option<String>,
+
+        /// Use the example input from the puzzle text
+        #[arg(long)]
+        example: bool,
+    },
+    /// Time parsing and solving a day, or every day, and compare against saved results
+    Bench {
+        /// Day to benchmark, or all of them if not given
+        day: Option<u8>,
+
+        /// Only time this part (1 or 2)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Timed runs of each stage
+        #[arg(long, default_value_t = 10)]
+        runs: usize,
+
+        /// Untimed runs before timing starts
+        #[arg(long, default_value_t = 1)]
+        warmup: usize,
+
+        /// File to save results to and compare against [default: bench.json in the package]
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+
+        /// Overwrite saved results instead of only filling in missing ones
+        #[arg(long)]
+        save: bool,
+
+        /// Flag a regression when the median is this many percent slower than the baseline
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Run every day and check the answers against the ledger of accepted ones
+    Verify {
+        /// Ledger of submitted answers [default: answers.toml in the package]
+        #[arg(long)]
+        ledger: Option<PathBuf>,
+    },
+    /// Download puzzle inputs which haven't been already
+    Fetch {
+        /// Days to download
+        #[arg(required = true, value_parser = clap::value_parser!(u8).range(1..=25))]
+        days: Vec<u8>,
+    },
+    /// Draw a picture of a day's puzzle, for the days which know how
+    Draw {
+        /// Day to draw
+        day: u8,
+
+        /// Use the example input from the puzzle text
+        #[arg(long)]
+        example: bool,
+
+        /// text, ansi, ppm or png. Images get written to stdout unless saving frames.
+        #[arg(long, default_value = "text")]
+        format: Format,
+
+        /// Save frames of an animation to this directory instead of drawing one picture
+        #[arg(long)]
+        frames: Option<PathBuf>,
+    },
+    /// Start a new day from the template and register it with the runner
+    New {
+        /// Day to create
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    init_logging(matches!(cli.command, Command::Run { .. }));
+    if let Some(threads) = cli.threads {
+        if let Err(e) = parallel::set_threads(threads) {
+            eprintln!("Warning: {e}");
+        }
+    }
+
+    let result = match cli.command {
+        Command::Run {
+            day,
+            part,
+            all,
+            input,
+            example,
+        } => {
+            let parts = parts(part);
+            let source = Source::from_arg(input.as_deref(), example);
+            // Benchmarks and verifying stay quiet, but somebody waiting on a run wants to know
+            // how it's going
+            progress::set_mode(Mode::detect());
+            let ledger = puzzle_ledger(&source);
+            if all {
+                days::DAYS
+                    .iter()
+                    .try_for_each(|(day, solver)| run(*day, *solver, &source, &parts, &ledger))
+            } else {
+                let day = day.unwrap();
+                match days::get(day) {
+                    Some(solver) => run(day, solver, &source, &parts, &ledger),
+                    None => Err(format!("Day {day} isn't solved yet").into()),
+                }
+            }
+        }
+        Command::Bench {
+            day,
+            part,
+            runs,
+            warmup,
+            baseline,
+            save,
+            threshold,
+        } => {
+            let parts = parts(part);
+            let config = bench::Config { warmup, runs };
+            let baseline = baseline.unwrap_or_else(|| package_path(bench::DEFAULT_BASELINE));
+            run_bench(day, &parts, &config, &baseline, save, threshold / 100.0)
+        }
+        Command::Verify { ledger } => {
+            verify(&ledger.unwrap_or_else(|| package_path(ledger::DEFAULT_LEDGER)))
+        }
+        Command::Fetch { days } => run_fetch(&days),
+        Command::Draw {
+            day,
+            example,
+            format,
+            frames,
+        } => draw(day, example, format, frames.as_deref()),
+        Command::New { day } => scaffold::new_day(Path::new(PACKAGE_ROOT), day)
+            .map(|created| {
+                for path in created {
+                    println!("Created {}", path.display());
+                }
+            })
+            .map_err(|e| e.into()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Log whatever RUST_LOG asks for. Without it, runs which log their progress because stderr isn't a
+// terminal still need those lines to show up.
+fn init_logging(running: bool) {
+    let mut builder = env_logger::Builder::from_default_env();
+    if running && Mode::detect() == Mode::Log && env::var_os("RUST_LOG").is_none() {
+        builder.filter_module("aoc2024::progress", LevelFilter::Info);
+    }
+    builder.init();
+}
+
+// Parse a day's input once and print the answer to each of the requested parts, with a warning
+// for any answer that the ledger says can't be right
+fn run(
+    day: u8,
+    solver: &dyn Solver,
+    source: &Source,
+    parts: &[Part],
+    ledger: &Ledger,
+) -> Result<(), Box<dyn Error>> {
+    let s = input::load(day, source)?;
+    let input = solver.parse(&s)?;
+    for &part in parts {
+        let answer = solver.solve(input.as_ref(), part);
+        println!("Day {day} part {part}: {answer}");
+        match ledger.check(day, part, &answer) {
+            Check::Correct | Check::Unknown => {}
+            check => eprintln!("Warning: day {day} part {part} answer {answer} {check}"),
+        }
+    }
+    Ok(())
+}
+
+// The ledger only knows about answers for the real puzzle input, so anything else gets an empty one
+fn puzzle_ledger(source: &Source) -> Ledger {
+    if *source != Source::Puzzle {
+        return Ledger::default();
+    }
+    let path = package_path(ledger::DEFAULT_LEDGER);
+    Ledger::load(&path).unwrap_or_else(|e| {
+        eprintln!("Warning: Unable to load {}: {e}", path.display());
+        Ledger::default()
+    })
+}
+
+// Run every part with an accepted answer in the ledger, and fail if any of them come out different
+fn verify(ledger_path: &Path) -> Result<(), Box<dyn Error>> {
+    let ledger = Ledger::load(ledger_path)
+        .map_err(|e| format!("Unable to load {}: {e}", ledger_path.display()))?;
+    let mut mismatches = 0;
+
+    for &(day, solver) in days::DAYS {
+        let parts: Vec<Part> = Part::ALL
+            .into_iter()
+            .filter(|&part| ledger.get(day, part).is_some_and(|r| r.answer.is_some()))
+            .collect();
+        if parts.is_empty() {
+            println!("Day {day}: no accepted answers");
+            continue;
+        }
+
+        let s = input::load(day, &Source::Puzzle)?;
+        let input = solver.parse(&s)?;
+        for part in parts {
+            let answer = solver.solve(input.as_ref(), part);
+            match ledger.check(day, part, &answer) {
+                Check::Correct => println!("Day {day} part {part}: {answer} ok"),
+                check => {
+                    mismatches += 1;
+                    println!("Day {day} part {part}: MISMATCH, {answer} {check}");
+                }
+            }
+        }
+    }
+
+    if mismatches > 0 {
+        return Err(format!("{mismatches} answer(s) didn't match the ledger").into());
+    }
+    Ok(())
+}
+
+// Benchmark some days, print how they did compared to the baseline, and save any new results.
+// Regressions make this fail, after everything has been run and saved.
+fn run_bench(
+    day: Option<u8>,
+    parts: &[Part],
+    config: &bench::Config,
+    baseline_path: &Path,
+    save: bool,
+    threshold: f64,
+) -> Result<(), Box<dyn Error>> {
+    let days = match day {
+        Some(day) => match days::get(day) {
+            Some(solver) => vec![(day, solver)],
+            None => return Err(format!("Day {day} isn't solved yet").into()),
+        },
+        None => days::DAYS.to_vec(),
+    };
+    let mut baseline = Baseline::load(baseline_path)
+        .map_err(|e| format!("Unable to load {}: {e}", baseline_path.display()))?;
+    let mut regressions = 0;
+
+    for (day, solver) in days {
+        let s = input::load(day, &Source::Puzzle)?;
+        for (stage, stats) in bench::bench_day(solver, &s, parts, config)? {
+            let comparison = match baseline.get(day, stage) {
+                Some(old) => {
+                    let change = stats.change_from(old);
+                    let flag = if change > threshold {
+                        regressions += 1;
+                        "  REGRESSION"
+                    } else {
+                        ""
+                    };
+                    format!("  ({:+.1}% vs {:.1?}){flag}", change * 100.0, old.median())
+                }
+                None => String::new(),
+            };
+            println!("Day {day:>2} {stage:<6}  {stats}{comparison}");
+
+            if save || baseline.get(day, stage).is_none() {
+                baseline.set(day, stage, stats);
+            }
+        }
+    }
+
+    baseline
+        .save(baseline_path)
+        .map_err(|e| format!("Unable to save {}: {e}", baseline_path.display()))?;
+
+    if regressions > 0 {
+        return Err(format!(
+            "{regressions} regression(s) against {}",
+            baseline_path.display()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+// Download inputs, or say where they already are
+fn run_fetch(days: &[u8]) -> Result<(), Box<dyn Error>> {
+    let config = fetch::Config::load(&package_path(fetch::CONFIG_FILE))?;
+    for &day in days {
+        match fetch::fetch(day, &config)? {
+            Fetched::Downloaded(path) => println!("Downloaded day {day} to {}", path.display()),
+            Fetched::Cached(path) => println!("Day {day} is already at {}", path.display()),
+        }
+    }
+    Ok(())
+}
+
+// Draw a day, either as one picture on stdout or as frames in a directory
+fn draw(
+    day: u8,
+    example: bool,
+    format: Format,
+    frames: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    if day != 6 {
+        return Err(format!("Day {day} doesn't know how to draw itself").into());
+    }
+    let s = input::load(day, &Source::from_arg(None, example))?;
+    let walker = Solution::parse(&day6::Day6, &s)?;
+    let renderer = Renderer::new(format, day6::palette);
+
+    match frames {
+        Some(dir) => {
+            let mut frames = Frames::new(renderer, dir)?;
+            day6::animate(&walker, &mut frames)?;
+            println!("Saved {} frames to {}", frames.len(), dir.display());
+        }
+        None => io::stdout().write_all(&renderer.render(&day6::picture(&walker)))?,
+    }
+    Ok(())
+}
+
+// A file which lives in the package directory
+fn package_path(name: &str) -> PathBuf {
+    Path::new(PACKAGE_ROOT).join(name)
+}
+
+// Which parts to run, given the --part argument
+fn parts(part: Option<u8>) -> Vec<Part> {
+    match part {
+        Some(p) => vec![Part::try_from(p).unwrap()],
+        None => Part::ALL.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_path() {
+        let baseline = package_path(bench::DEFAULT_BASELINE);
+        assert!(baseline.is_absolute());
+        assert_eq!(baseline.parent(), Some(Path::new(PACKAGE_ROOT)));
+    }
+
+    #[test]
+    fn test_input_conflicts() {
+        assert!(Cli::try_parse_from(["aoc", "run", "6", "--input", "foo.txt"]).is_ok());
+        assert!(
+            Cli::try_parse_from(["aoc", "run", "6", "--example", "--input", "foo.txt"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["aoc", "run", "--all", "--input", "foo.txt"]).is_err());
+    }
+}
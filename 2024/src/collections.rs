@@ -0,0 +1,213 @@
+use std::collections::hash_map;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops;
+
+/// A multiset, which counts how many of each item there are. Items which aren't there have a
+/// count of 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Counter<T: Hash + Eq> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Hash + Eq> Default for Counter<T> {
+    fn default() -> Self {
+        Counter {
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Hash + Eq> Counter<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `n` more of `item`
+    pub fn add(&mut self, item: T, n: usize) {
+        if n > 0 {
+            *self.counts.entry(item).or_default() += n;
+        }
+    }
+
+    /// How many of `item` there are
+    pub fn get(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    /// How many items there are altogether, counting duplicates
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// How many distinct items there are
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Each distinct item with its count, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(item, &n)| (item, n))
+    }
+
+    /// The `k` items with the highest counts, highest first. Ties go to the smaller item so the
+    /// result doesn't depend on hash order.
+    pub fn most_common(&self, k: usize) -> Vec<(&T, usize)>
+    where
+        T: Ord,
+    {
+        let mut items: Vec<_> = self.iter().collect();
+        items.sort_by(|(a, m), (b, n)| n.cmp(m).then_with(|| a.cmp(b)));
+        items.truncate(k);
+        items
+    }
+
+    /// Replace every item with whatever `rule` turns it into, keeping the counts. Each item is
+    /// only looked at once no matter how many of it there are, which is what makes this fast when
+    /// there are lots of duplicates.
+    pub fn evolve<F, I>(&self, mut rule: F) -> Counter<T>
+    where
+        F: FnMut(&T) -> I,
+        I: IntoIterator<Item = T>,
+    {
+        let mut next = Counter::new();
+        for (item, n) in self.iter() {
+            for new in rule(item) {
+                next.add(new, n);
+            }
+        }
+        next
+    }
+}
+
+impl<T: Hash + Eq> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Hash + Eq> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add(item, 1);
+        }
+    }
+}
+
+impl<T: Hash + Eq> IntoIterator for Counter<T> {
+    type Item = (T, usize);
+    type IntoIter = hash_map::IntoIter<T, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+// Combine two counters item by item
+fn combine<T, F>(a: &Counter<T>, b: &Counter<T>, f: F) -> Counter<T>
+where
+    T: Hash + Eq + Clone,
+    F: Fn(usize, usize) -> usize,
+{
+    let mut counter = Counter::new();
+    for item in a.counts.keys().chain(b.counts.keys()) {
+        if !counter.counts.contains_key(item) {
+            counter.add(item.clone(), f(a.get(item), b.get(item)));
+        }
+    }
+    counter
+}
+
+/// Sum: the counts added together
+impl<T: Hash + Eq + Clone> ops::Add for &Counter<T> {
+    type Output = Counter<T>;
+
+    fn add(self, other: Self) -> Counter<T> {
+        combine(self, other, |a, b| a + b)
+    }
+}
+
+/// Union: the larger of the two counts
+impl<T: Hash + Eq + Clone> ops::BitOr for &Counter<T> {
+    type Output = Counter<T>;
+
+    fn bitor(self, other: Self) -> Counter<T> {
+        combine(self, other, usize::max)
+    }
+}
+
+/// Intersection: the smaller of the two counts
+impl<T: Hash + Eq + Clone> ops::BitAnd for &Counter<T> {
+    type Output = Counter<T>;
+
+    fn bitand(self, other: Self) -> Counter<T> {
+        combine(self, other, usize::min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts() {
+        let mut counter: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!(counter.get(&'a'), 5);
+        assert_eq!(counter.get(&'d'), 1);
+        assert_eq!(counter.get(&'z'), 0);
+        assert_eq!(counter.total(), 11);
+        assert_eq!(counter.len(), 5);
+        // b and r tie, so they're in order
+        assert_eq!(
+            counter.most_common(3),
+            vec![(&'a', 5), (&'b', 2), (&'r', 2)]
+        );
+
+        counter.add('z', 3);
+        counter.add('y', 0);
+        assert_eq!(counter.get(&'z'), 3);
+        assert_eq!(counter.len(), 6);
+        assert_eq!(counter.most_common(100).len(), 6);
+    }
+
+    #[test]
+    fn test_operators() {
+        let a: Counter<char> = "aaab".chars().collect();
+        let b: Counter<char> = "abbc".chars().collect();
+
+        let sum = &a + &b;
+        assert_eq!((sum.get(&'a'), sum.get(&'b'), sum.get(&'c')), (4, 3, 1));
+        let union = &a | &b;
+        assert_eq!(
+            (union.get(&'a'), union.get(&'b'), union.get(&'c')),
+            (3, 2, 1)
+        );
+        let intersection = &a & &b;
+        assert_eq!(intersection.get(&'a'), 1);
+        assert_eq!(intersection.get(&'b'), 1);
+        // Nothing with a count of zero is kept around
+        assert_eq!(intersection.len(), 2);
+    }
+
+    #[test]
+    fn test_evolve() {
+        // Every cell splits in two, and the grown up ones die off
+        let cells: Counter<u8> = [0, 0, 1].into_iter().collect();
+        let next = cells.evolve(|&age| {
+            if age < 1 {
+                vec![age + 1, age + 1]
+            } else {
+                vec![]
+            }
+        });
+        assert_eq!(next.get(&1), 4);
+        assert_eq!(next.total(), 4);
+        assert!(next.evolve(|_| None).is_empty());
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Cycle detection for sequences x0, f(x0), f(f(x0)), ... where the next state only depends on the
+// current one. Any such sequence over a finite set of states eventually repeats, so these always
+// find a cycle as long as the state space is finite. Sequences which can end (like a guard walking
+// off the map) can be modelled with an Option state where None steps to itself.
+// https://en.wikipedia.org/wiki/Cycle_detection
+
+/// Where a sequence starts repeating itself
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<S> {
+    /// How many steps until we first enter the cycle (mu)
+    pub start: usize,
+    /// How many steps it takes to go around the cycle once (lambda)
+    pub len: usize,
+    /// The state at step `start`, which is the first one that gets repeated
+    pub entry: S,
+}
+
+impl<S> Cycle<S> {
+    /// The earliest step which has the same state as step `n`
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+}
+
+/// Floyd's tortoise and hare. Only needs to compare states, and keeps two of them around at once.
+pub fn floyd<S, F>(start: S, mut step: F) -> Cycle<S>
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // Find a point in the cycle, where the hare has gone around some number of times more than
+    // the tortoise
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    // The distance between them is a multiple of the cycle length, so moving both at the same
+    // speed from there and from the beginning makes them meet at the start of the cycle
+    let mut mu = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    let mut lambda = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    Cycle {
+        start: mu,
+        len: lambda,
+        entry: tortoise,
+    }
+}
+
+/// Brent's algorithm, which finds the cycle length first by teleporting the tortoise to the hare
+/// at powers of two. Needs fewer steps than Floyd's, which matters when stepping is expensive.
+pub fn brent<S, F>(start: S, mut step: F) -> Cycle<S>
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    // Start the hare one cycle length ahead, then they'll meet at the start of the cycle
+    tortoise = start.clone();
+    hare = start;
+    for _ in 0..lambda {
+        hare = step(&hare);
+    }
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    Cycle {
+        start: mu,
+        len: lambda,
+        entry: tortoise,
+    }
+}
+
+/// Find a cycle by remembering when we saw every state. Uses more memory than `floyd` or `brent`,
+/// but only steps through the sequence once.
+pub fn find<S, F>(start: S, mut step: F) -> Cycle<S>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut state = start;
+    for i in 0.. {
+        if let Some(&first) = seen.get(&state) {
+            return Cycle {
+                start: first,
+                len: i - first,
+                entry: state,
+            };
+        }
+        let next = step(&state);
+        seen.insert(state, i);
+        state = next;
+    }
+    unreachable!()
+}
+
+/// The state after `n` steps. Once the sequence repeats we skip ahead by whole cycles, so `n` can
+/// be as big as you like as long as the cycle shows up reasonably early.
+pub fn simulate<S, F>(start: S, mut step: F, n: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    let mut state = start;
+    for i in 0..n {
+        if let Some(&first) = seen.get(&state) {
+            let len = i - first;
+            return history.swap_remove(first + (n - first) % len);
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), i);
+        history.push(state);
+        state = next;
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 2 -> ...
+    fn small(x: &u32) -> u32 {
+        if *x == 4 {
+            2
+        } else {
+            x + 1
+        }
+    }
+
+    // A pseudo-random looking sequence with a long lead-in
+    fn lcg(x: &u64) -> u64 {
+        (x * x + 1) % 2_003
+    }
+
+    #[test]
+    fn test_small() {
+        let expected = Cycle {
+            start: 2,
+            len: 3,
+            entry: 2,
+        };
+        assert_eq!(floyd(0, small), expected);
+        assert_eq!(brent(0, small), expected);
+        assert_eq!(find(0, small), expected);
+
+        // Already in the cycle
+        assert_eq!(brent(3, small).start, 0);
+        assert_eq!(brent(3, small).len, 3);
+    }
+
+    #[test]
+    fn test_agree() {
+        for x0 in [0, 3, 42, 1_000] {
+            let expected = find(x0, lcg);
+            assert_eq!(floyd(x0, lcg), expected);
+            assert_eq!(brent(x0, lcg), expected);
+        }
+    }
+
+    #[test]
+    fn test_simulate() {
+        for n in 0..20 {
+            let mut x = 0;
+            for _ in 0..n {
+                x = small(&x);
+            }
+            assert_eq!(simulate(0, small, n), x, "{n} steps");
+        }
+        assert_eq!(simulate(0, small, 1_000_000_000_000_000), 4);
+
+        // Sequences which end turn into a fixed point
+        let countdown = |x: &Option<u32>| x.and_then(|x| x.checked_sub(1));
+        assert_eq!(simulate(Some(5), countdown, 3), Some(2));
+        assert_eq!(simulate(Some(5), countdown, 1_000_000_000_000_000), None);
+        assert_eq!(find(Some(5), countdown).start, 6);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::collections::Counter;
+use crate::parse::Span;
+use crate::{Answer, AocError, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = (Vec<i32>, Vec<i32>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        get_lists(input)
+    }
+
+    fn part1(&self, (left, right): &Self::Input) -> Answer {
+        part1(left, right).into()
+    }
+
+    fn part2(&self, (left, right): &Self::Input) -> Answer {
+        part2(left, right).into()
+    }
+}
+
+fn part1(left: &[i32], right: &[i32]) -> u32 {
+    let mut acc = 0;
+    for (l, r) in left.iter().zip(right.iter()) {
+        acc += l.abs_diff(*r);
+    }
+
+    acc
+}
+
+fn part2(left: &[i32], right: &[i32]) -> i32 {
+    // Count how many times each item occurs in the right list
+    let right_counts: Counter<i32> = right.iter().copied().collect();
+
+    let mut score = 0;
+    for l in left {
+        score += l * right_counts.get(l) as i32;
+    }
+
+    score
+}
+
+fn get_lists(input: &str) -> Result<(Vec<i32>, Vec<i32>), AocError> {
+    let pairs = Span::new(input).map_lines(|line| line.fields::<(i32, i32)>())?;
+    let (mut left, mut right): (Vec<i32>, Vec<i32>) = pairs.into_iter().unzip();
+    left.sort();
+    right.sort();
+
+    Ok((left, right))
+}
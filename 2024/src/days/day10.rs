@@ -0,0 +1,118 @@
+use log::debug;
+
+use crate::geom::UPoint;
+use crate::grid::Grid;
+use crate::memo::Memo;
+use crate::search;
+use crate::{Answer, AocError, Solution};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Map;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        Map::from_str(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        let (score, _) = walk_map(input);
+        score.into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let (_, rating) = walk_map(input);
+        rating.into()
+    }
+}
+
+const MAX_HEIGHT: u8 = 9;
+
+pub struct Map {
+    grid: Grid<u8>,
+    starts: Vec<UPoint>,
+}
+
+impl Map {
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let grid = Grid::parse(s, |c| c.to_digit(10).map(|d| d as u8))?;
+        let starts = grid.positions(|&height| height == 0).collect();
+
+        Ok(Map { grid, starts })
+    }
+}
+
+// Do walks from each starting point on the map. Returns the "score" (number of peaks found) and
+// "rating" (number of paths which end up at a peak)
+fn walk_map(map: &Map) -> (usize, usize) {
+    let mut score = 0;
+    let mut rating = 0;
+    // How many trails go from a position up to a peak doesn't depend on where we started, so
+    // the ratings share one cache
+    let mut memo = Memo::new("day10 ratings");
+    for &start in map.starts.iter() {
+        debug!("Starting walk at {start}");
+        let is_peak = |pos: &UPoint| map.grid[*pos] == MAX_HEIGHT;
+
+        let walk_score = search::reachable(start, |&pos| uphill(map, pos))
+            .iter()
+            .filter(|pos| is_peak(pos))
+            .count();
+        let walk_rating = trails(map, start, &mut memo);
+
+        debug!("Got walk score of {walk_score} from {start}");
+        debug!("Got walk rating of {walk_rating} from {start}");
+        score += walk_score;
+        rating += walk_rating;
+    }
+
+    (score, rating)
+}
+
+// Number of distinct trails from `pos` up to any peak
+fn trails(map: &Map, pos: UPoint, memo: &mut Memo<UPoint, usize>) -> usize {
+    memo.get(pos, |memo| {
+        if map.grid[pos] == MAX_HEIGHT {
+            1
+        } else {
+            uphill(map, pos).map(|next| trails(map, next, memo)).sum()
+        }
+    })
+}
+
+// Neighbouring positions which are exactly one step higher, which are the only ones a trail can
+// go to next
+fn uphill(map: &Map, pos: UPoint) -> impl Iterator<Item = UPoint> + '_ {
+    let next = map.grid[pos] + 1;
+    map.grid
+        .neighbours4(pos)
+        .filter(move |(_, &height)| height == next)
+        .map(|(p, _)| p)
+}
+
+#[cfg(test)]
+mod day10_tests {
+    use super::*;
+    use crate::input;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[test]
+    fn test_from_str() {
+        init();
+        let m = Map::from_str(&input::example(10).unwrap()).unwrap();
+        assert_eq!(m.grid[UPoint::new(0, 0)], 8);
+        assert_eq!(m.grid[UPoint::new(7, 7)], 2);
+    }
+
+    #[test]
+    fn test_walk() {
+        init();
+        let m = Map::from_str(&input::example(10).unwrap()).unwrap();
+        let (score, rating) = walk_map(&m);
+        assert_eq!(score, 36);
+        assert_eq!(rating, 81);
+    }
+}
@@ -1,30 +1,41 @@
-use indicatif::{ProgressBar, ProgressStyle};
-use std::collections::HashMap;
 use std::fmt;
-use std::num::ParseIntError;
+use std::iter;
 
-fn main() {
-    env_logger::init();
-    let stones = Stones::try_from(include_str!("../../data/day11.txt")).unwrap();
-    println!("Part 1: {}", part1(stones.clone()));
-    println!("Part 2: {}", part2(stones));
+use crate::collections::Counter;
+use crate::parse::Span;
+use crate::progress::Progress;
+use crate::{Answer, AocError, Solution};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Stones;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        Stones::try_from(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input.clone()).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input.clone()).into()
+    }
 }
 
 type Stone = u64;
 
 #[derive(Clone)]
-struct Stones {
+pub struct Stones {
     stones: Vec<Stone>,
 }
 
 impl TryFrom<&str> for Stones {
-    type Error = ParseIntError;
+    type Error = AocError;
 
     fn try_from(source: &str) -> Result<Self, Self::Error> {
-        let mut stones = Vec::new();
-        for stone in source.split_whitespace() {
-            stones.push(stone.parse()?);
-        }
+        let stones = Span::new(source).parse()?;
         Ok(Stones { stones })
     }
 }
@@ -52,7 +63,7 @@ impl Iterator for Stones {
             let stone_digits = stone.to_string();
             if stone == 0 {
                 new_stones.push(1);
-            } else if stone_digits.len() % 2 == 0 {
+            } else if stone_digits.len().is_multiple_of(2) {
                 // Replace with two stones
                 let (l, r) = stone_digits.split_at(stone_digits.len() / 2);
                 new_stones.push(l.parse().unwrap());
@@ -68,15 +79,7 @@ impl Iterator for Stones {
 
 // How many stones do we have after 25 iterations?
 fn part1(stones: Stones) -> usize {
-    let mut count: usize = 0;
-    let bar = ProgressBar::new_spinner().with_style(
-        ProgressStyle::with_template("{spinner} {human_pos} [{elapsed_precise}] {per_sec}")
-            .unwrap(),
-    );
-    for stone in stones.stones {
-        count += process(stone, 25, &mut HashMap::new(), &bar);
-    }
-    count
+    count_after(&stones, 25)
 }
 
 // How many stones do we have after 75 iterations?
@@ -85,55 +88,34 @@ fn part1(stones: Stones) -> usize {
 // hundreds of millions of stones to process - it's many gigabytes, even with memory optimization
 // (u64 -> u32, holding fewer copies in memory, etc.)
 //
-// The stones are indepedent from each other, so we can do all 75 iterations on one at a time. We
-// can do this recursively one stone at a time. The call stack should only get as deep as our
-// number of iterations.
+// The order of the stones never matters, and there's only a few thousand different numbers which
+// ever show up, so we just keep count of how many stones have each number.
 fn part2(stones: Stones) -> usize {
-    let mut count: usize = 0;
-    let bar = ProgressBar::new_spinner().with_style(
-        ProgressStyle::with_template("{spinner} {human_pos} [{elapsed_precise}] {per_sec}")
-            .unwrap(),
-    );
-    for stone in stones.stones {
-        count += process(stone, 75, &mut HashMap::new(), &bar);
-    }
-    count
+    count_after(&stones, 75)
 }
 
-// Recursively process a stone
-fn process(
-    stone: Stone,
-    iterations: u8,
-    cache: &mut HashMap<(Stone, u8), usize>,
-    bar: &ProgressBar,
-) -> usize {
-    match cache.get(&(stone, iterations)) {
-        Some(&count) => {
-            bar.inc(count.try_into().unwrap());
-            return count;
-        }
-        None => {}
-    }
-
-    if iterations == 0 {
-        bar.inc(1);
-        return 1;
+// How many stones there are after blinking `blinks` times
+fn count_after(stones: &Stones, blinks: u64) -> usize {
+    let progress = Progress::new("day11 blinks", blinks);
+    let mut counts: Counter<Stone> = stones.stones.iter().copied().collect();
+    for _ in 0..blinks {
+        counts = counts.evolve(|&stone| blink(stone));
+        progress.inc(1);
     }
+    counts.total()
+}
 
-    let mut count = 0;
-    if stone == 0 {
-        count += process(1, iterations - 1, cache, bar);
-    } else if count_digits(&stone) % 2 == 0 {
+// What a single stone turns into when we blink
+fn blink(stone: Stone) -> impl Iterator<Item = Stone> {
+    let (first, second) = if stone == 0 {
+        (1, None)
+    } else if count_digits(&stone).is_multiple_of(2) {
         let (l, r) = split_number(stone);
-        count += process(l, iterations - 1, cache, bar);
-        count += process(r, iterations - 1, cache, bar);
+        (l, Some(r))
     } else {
-        count += process(stone * 2024, iterations - 1, cache, bar);
-    }
-
-    cache.insert((stone, iterations), count);
-
-    count
+        (stone * 2024, None)
+    };
+    iter::once(first).chain(second)
 }
 
 fn count_digits(s: &Stone) -> u32 {
@@ -152,7 +134,7 @@ fn count_digits(s: &Stone) -> u32 {
 // Will panic if given a number with an odd number of digits
 fn split_number(s: Stone) -> (Stone, Stone) {
     let num_digits = count_digits(&s);
-    assert!(num_digits % 2 == 0);
+    assert!(num_digits.is_multiple_of(2));
 
     // l is the left side, r is right side
     // We'll basically "pop" off digits from the end by taking l % 10 to get the digit and l / 10
@@ -160,7 +142,7 @@ fn split_number(s: Stone) -> (Stone, Stone) {
     //
     // We "push left" the popped digits in r by multiplying them by x, which gets multiplied by 10
     // each time.
-    let mut l = s.clone();
+    let mut l = s;
     let mut r = 0;
     let mut x = 1;
     for _ in 0..(num_digits / 2) {
@@ -175,6 +157,8 @@ fn split_number(s: Stone) -> (Stone, Stone) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::{self, Source};
+    use crate::memo::Memo;
 
     const TEST_STONES: &str = "125 17";
 
@@ -182,11 +166,36 @@ mod tests {
         let _ = env_logger::builder().is_test(true).try_init();
     }
 
+    // The recursive way of counting, one stone at a time, which we check count_after() against. Lots
+    // of stones end up with the same numbers, so the cache is shared between all of them.
+    fn process(stone: Stone, iterations: u8, memo: &mut Memo<(Stone, u8), usize>) -> usize {
+        if iterations == 0 {
+            return 1;
+        }
+
+        memo.get((stone, iterations), |memo| {
+            blink(stone)
+                .map(|next| process(next, iterations - 1, memo))
+                .sum()
+        })
+    }
+
     #[test]
     fn test_parse() {
         init();
         let stones = Stones::try_from(TEST_STONES).unwrap();
         assert_eq!(stones.stones, vec![125, 17]);
+
+        let e = Stones::try_from("125 -17").err();
+        assert!(matches!(
+            e,
+            Some(AocError::Parse {
+                line: 1,
+                column: 5,
+                len: 3,
+                ..
+            })
+        ));
     }
 
     #[test]
@@ -220,10 +229,11 @@ mod tests {
     #[test]
     fn test_recursive_method_real() {
         init();
-        let stones = Stones::try_from(include_str!("../../data/day11.txt")).unwrap();
+        let stones = Stones::try_from(input::load(11, &Source::Puzzle).unwrap().as_str()).unwrap();
+        let mut memo = Memo::new("test");
         let mut count = 0;
         for stone in stones.stones {
-            count += process(stone, 25, &mut HashMap::new(), &ProgressBar::hidden());
+            count += process(stone, 25, &mut memo);
         }
         assert_eq!(count, 191690);
     }
@@ -232,10 +242,19 @@ mod tests {
     fn test_process_with_cache() {
         init();
         let stones = Stones::try_from(TEST_STONES).unwrap();
+        let mut memo = Memo::new("test");
         let mut count = 0;
         for stone in stones.stones {
-            count += process(stone, 25, &mut HashMap::new(), &ProgressBar::hidden());
+            count += process(stone, 25, &mut memo);
         }
         assert_eq!(count, 55312);
     }
+
+    #[test]
+    fn test_count_after() {
+        init();
+        let stones = Stones::try_from(TEST_STONES).unwrap();
+        assert_eq!(count_after(&stones, 6), 22);
+        assert_eq!(count_after(&stones, 25), 55312);
+    }
 }
@@ -0,0 +1,138 @@
+use log::debug;
+use std::collections::HashSet;
+
+use crate::geom::{Direction, UPoint};
+use crate::grid::Grid;
+use crate::search;
+use crate::{Answer, AocError, Solution};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Garden;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        Garden::try_from(input)
+    }
+
+    // Total fencing price, which is the sum of area * perimeter for each region
+    fn part1(&self, input: &Self::Input) -> Answer {
+        input
+            .regions
+            .iter()
+            .map(|r| r.area * r.perimeter as usize)
+            .sum::<usize>()
+            .into()
+    }
+
+    fn part2(&self, _input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+pub struct Garden {
+    #[allow(dead_code)]
+    grid: Grid<char>,
+    regions: Vec<Region>,
+}
+
+impl TryFrom<&str> for Garden {
+    type Error = AocError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let grid = Grid::parse(s, Some)?;
+
+        // Flood fill out from every point which isn't in a region yet to find the rest of its
+        // region
+        let mut regions = Vec::new();
+        let mut seen = HashSet::new();
+        for (start, &c) in grid.iter() {
+            if seen.contains(&start) {
+                continue;
+            }
+            let points = search::reachable(start, |&p| {
+                grid.neighbours4(p)
+                    .filter(move |(_, &n)| n == c)
+                    .map(|(q, _)| q)
+            });
+            seen.extend(points.iter().copied());
+
+            debug!("Found a {c} region with {} points", points.len());
+            regions.push(Region::new(c, points));
+        }
+
+        Ok(Self { grid, regions })
+    }
+}
+
+// The character and points are only looked at when debugging for now
+#[allow(dead_code)]
+#[derive(Debug)]
+struct Region {
+    character: char,
+    points: HashSet<UPoint>,
+    area: usize,
+    perimeter: u32,
+}
+
+impl Region {
+    fn new(character: char, points: HashSet<UPoint>) -> Self {
+        let mut perimeter = 0;
+        for &point in &points {
+            for direction in Direction::ALL {
+                // Anything not in the region needs a fence, including spots off the grid
+                match point + direction.offset() {
+                    Some(p) if points.contains(&p) => {}
+                    _ => perimeter += 1,
+                }
+            }
+        }
+        Region {
+            character,
+            area: points.len(),
+            perimeter,
+            points,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[test]
+    fn test_from_str() {
+        init();
+        let g = Garden::try_from(input::example(12).unwrap().as_str()).unwrap();
+        for reg in g.regions {
+            if reg.character == 'R' {
+                let mut p: Vec<&UPoint> = reg.points.iter().collect();
+                p.sort();
+                debug!("R points: {p:?}");
+                assert_eq!(reg.area, 12);
+                assert_eq!(reg.perimeter, 18);
+            }
+            if reg.character == 'E' {
+                let mut p: Vec<&UPoint> = reg.points.iter().collect();
+                p.sort();
+                debug!("E points: {p:?}");
+                assert_eq!(reg.area, 13);
+                assert_eq!(reg.perimeter, 18);
+            }
+        }
+    }
+
+    #[test]
+    fn test_part1() {
+        init();
+        let g = Garden::try_from(input::example(12).unwrap().as_str()).unwrap();
+        // There are two separate C and I regions, which need to be priced separately
+        assert_eq!(g.regions.len(), 11);
+        assert_eq!(Day12.part1(&g), Answer::UInt(1930));
+    }
+}
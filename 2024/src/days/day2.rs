@@ -0,0 +1,114 @@
+use crate::parse::Span;
+use crate::{Answer, AocError, Solution};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
+fn part1(reports: &[Vec<i32>]) -> usize {
+    reports.iter().filter(|r| is_safe(r)).count()
+}
+
+fn part2(reports: &[Vec<i32>]) -> usize {
+    let mut safe_count = 0;
+    for report in reports {
+        if is_safe(report) {
+            safe_count += 1;
+        } else {
+            // Try removing individual levels to see if the report is safe then
+            for idx in 0..report.len() {
+                let mut possible = report.clone();
+                possible.remove(idx);
+                if is_safe(&possible) {
+                    safe_count += 1;
+                    break;
+                }
+            }
+        }
+    }
+
+    safe_count
+}
+
+// Returns whether or not a report is safe
+fn is_safe(report: &[i32]) -> bool {
+    // Part 2 can take a level out of a report with only two, and one level is always safe
+    if report.len() < 2 {
+        return true;
+    }
+
+    let mut ascending = false;
+    if report[0] < report[1] {
+        ascending = true;
+    }
+
+    let mut last = report[0];
+    for level in &report[1..] {
+        let diff = level - last;
+        if (diff < 0 && ascending) || (diff > 0 && !ascending) || diff.abs() < 1 || diff.abs() > 3 {
+            return false;
+        }
+        last = *level;
+    }
+
+    true
+}
+
+fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, AocError> {
+    Span::new(input).map_lines(|line| {
+        let report: Vec<i32> = line.parse()?;
+        // It takes two levels to tell which way a report is going
+        if report.len() < 2 {
+            return Err(line.expected("a report with at least two levels"));
+        }
+        Ok(report)
+    })
+}
+
+#[cfg(test)]
+mod day2_tests {
+    use super::*;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[test]
+    fn test_short_report() {
+        init();
+        let e = parse_input("7 6 4 2 1\n5\n").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "Line 2, column 1: expected a report with at least two levels"
+        );
+
+        // Anything that isn't a number is an error, not just something in between the numbers
+        let e = parse_input("1 2 x 3\n").unwrap_err();
+        assert!(matches!(
+            e,
+            AocError::Parse {
+                line: 1,
+                column: 5,
+                len: 1,
+                ..
+            }
+        ));
+
+        // Taking a level out of a two level report leaves one, which is fine
+        assert_eq!(part2(&[vec![1, 9]]), 1);
+    }
+}
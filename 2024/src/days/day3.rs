@@ -0,0 +1,84 @@
+use regex::Regex;
+
+use crate::parse::Span;
+use crate::{Answer, AocError, Solution};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[derive(Debug)]
+pub enum Instruction {
+    Mul { x: i32, y: i32 },
+    Do,
+    Dont,
+}
+
+fn part1(instructions: &[Instruction]) -> i32 {
+    instructions.iter().fold(0, |acc, i| acc + compute(i))
+}
+
+fn part2(instructions: &[Instruction]) -> i32 {
+    let mut result = 0;
+    let mut operate = true;
+    for instr in instructions {
+        match instr {
+            Instruction::Mul { x, y } => {
+                if operate {
+                    result += x * y;
+                }
+            }
+            Instruction::Do => {
+                operate = true;
+            }
+            Instruction::Dont => {
+                operate = false;
+            }
+        }
+    }
+    result
+}
+
+fn compute(instr: &Instruction) -> i32 {
+    match instr {
+        Instruction::Mul { x, y } => x * y,
+        Instruction::Do | Instruction::Dont => 0,
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Instruction>, AocError> {
+    let span = Span::new(input);
+    let mut instructions = Vec::new();
+
+    let re = Regex::new(r"(?<instr>mul|do|don't)\((?<args>[\d,]+)?\)").unwrap();
+    for capture in re.captures_iter(input) {
+        let whole = span.of(capture.get(0).unwrap().as_str());
+        let found = match &capture["instr"] {
+            "mul" => {
+                let args = capture.name("args").map_or(whole, |m| span.of(m.as_str()));
+                let (x, y) = args.record(",")?;
+                Instruction::Mul { x, y }
+            }
+            "do" => Instruction::Do,
+            "don't" => Instruction::Dont,
+            _ => return Err(whole.expected("mul, do or don't")),
+        };
+        instructions.push(found);
+    }
+
+    Ok(instructions)
+}
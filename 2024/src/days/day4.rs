@@ -0,0 +1,98 @@
+use log::debug;
+
+use crate::geom::Direction8;
+use crate::grid::Grid;
+use crate::{Answer, AocError, Solution};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Grid<char>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
+const WORD: [char; 4] = ['X', 'M', 'A', 'S'];
+
+fn part1(grid: &Grid<char>) -> usize {
+    let mut count = 0;
+
+    for start in grid.positions(|&c| c == WORD[0]) {
+        debug!("Found the start of a word at {start}");
+
+        // Walk out from the start in every direction and see if we spell out the word
+        for direction in Direction8::ALL {
+            let letters = grid.ray(start, direction.offset()).map(|(_, &c)| c);
+            if letters.take(WORD.len()).eq(WORD) {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+fn part2(grid: &Grid<char>) -> usize {
+    let mut count = 0;
+
+    for pos in grid.positions(|&c| c == 'A') {
+        let peek = |dir: Direction8| grid.offset(pos, dir.offset()).map(|p| grid[p]);
+
+        // check northwest + southeast
+        let (Some(nw), Some(se)) = (peek(Direction8::NorthWest), peek(Direction8::SouthEast))
+        else {
+            continue;
+        };
+        if (nw, se) != ('M', 'S') && (nw, se) != ('S', 'M') {
+            continue;
+        }
+
+        // check northeast + southwest
+        let (Some(ne), Some(sw)) = (peek(Direction8::NorthEast), peek(Direction8::SouthWest))
+        else {
+            continue;
+        };
+        if (ne, sw) != ('M', 'S') && (ne, sw) != ('S', 'M') {
+            continue;
+        }
+
+        count += 1;
+    }
+
+    count
+}
+
+fn parse_input(input: &str) -> Result<Grid<char>, AocError> {
+    Grid::parse(input, Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+
+    // Make sure part 1 works with the test input
+    #[test]
+    fn test_part1() {
+        let input = parse_input(&input::example(4).unwrap()).unwrap();
+        let result = part1(&input);
+        assert_eq!(result, 18);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(&input::example(4).unwrap()).unwrap();
+        let result = part2(&input);
+        assert_eq!(result, 9);
+    }
+}
@@ -0,0 +1,159 @@
+use log::{debug, warn};
+
+use crate::error::ParseErrorKind;
+use crate::order::PartialOrder;
+use crate::parse::Span;
+use crate::{Answer, AocError, Solution};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (Rules, Updates);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, (rules, updates): &Self::Input) -> Answer {
+        part1(rules, updates.clone()).into()
+    }
+
+    fn part2(&self, (rules, updates): &Self::Input) -> Answer {
+        part2(rules, updates.clone()).into()
+    }
+}
+
+// Page ordering rules, where "a|b" means page a must come before page b
+pub type Rules = PartialOrder<u32>;
+
+pub type Updates = Vec<Vec<u32>>;
+
+fn parse_input(s: &str) -> Result<(Rules, Updates), AocError> {
+    // Page ordering rules, then a blank line, then updates which are comma-separated lists
+    let input = Span::new(s);
+    let mut sections = input.sections();
+    let (Some(rules_section), Some(updates_section)) = (sections.next(), sections.next()) else {
+        return Err(input.expected_after("rules and updates separated by a blank line"));
+    };
+
+    let rules: Rules = rules_section
+        .map_lines(|line| line.record::<(u32, u32)>("|"))?
+        .into_iter()
+        .collect();
+
+    // The rules as a whole go round in circles, but they'd better not for the pages in any one
+    // update or there's no right way to order it
+    let updates = updates_section.map_lines(|line| {
+        let pages = line
+            .split(",")
+            .map(|page| page.parse())
+            .collect::<Result<Vec<u32>, _>>()?;
+        match rules.sort(&pages) {
+            Ok(_) => Ok(pages),
+            Err(e) => Err(line.error(ParseErrorKind::Invalid(format!("rules form a cycle: {e}")))),
+        }
+    })?;
+
+    Ok((rules, updates))
+}
+
+fn part1(rules: &Rules, updates: Updates) -> u32 {
+    // Find all correct updates
+    let (correct_updates, _) = bucket_updates(rules, updates);
+
+    // Get the sum of the middle number from each update
+    correct_updates
+        .into_iter()
+        .fold(0, |acc, u| acc + u[u.len() / 2])
+}
+
+fn part2(rules: &Rules, updates: Updates) -> u32 {
+    let (_, mut incorrect) = bucket_updates(rules, updates);
+
+    for update in &mut incorrect {
+        // Fix the incorrect updates by sorting them according to the rules
+        let sorted = rules
+            .sort(update)
+            .expect("Updates were checked for cycles when parsing");
+        if !sorted.unique {
+            warn!("More than one way to order {update:?}, the middle page might be wrong");
+        }
+        *update = sorted.items;
+    }
+
+    incorrect.into_iter().fold(0, |acc, u| acc + u[u.len() / 2])
+}
+
+// Bucket updates into two lists: correct ones an incorrect ones
+fn bucket_updates(rules: &Rules, updates: Updates) -> (Updates, Updates) {
+    let mut correct: Updates = Vec::new();
+    let mut incorrect: Updates = Vec::new();
+
+    for update in updates {
+        let good = rules.is_sorted(&update);
+        if good {
+            debug!("Good update: {:?}", update);
+            correct.push(update);
+        } else {
+            debug!("Bad update: {:?}", update);
+            incorrect.push(update);
+        }
+    }
+
+    (correct, incorrect)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[test]
+    fn test_part1() {
+        init();
+        let (rules, updates) = parse_input(&input::example(5).unwrap()).unwrap();
+        assert!(!rules.is_empty());
+        assert!(!updates.is_empty());
+        let result = part1(&rules, updates);
+        assert_eq!(result, 143);
+    }
+
+    #[test]
+    fn test_part2() {
+        init();
+        let (rules, updates) = parse_input(&input::example(5).unwrap()).unwrap();
+        assert!(!rules.is_empty());
+        assert!(!updates.is_empty());
+        let result = part2(&rules, updates);
+        assert_eq!(result, 123);
+    }
+
+    #[test]
+    fn test_bad_page() {
+        init();
+        let e = parse_input("1|2\n\n1,2\n1;2\n").unwrap_err();
+        assert!(matches!(
+            e,
+            AocError::Parse {
+                line: 4,
+                column: 1,
+                len: 3,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_cycle() {
+        init();
+        let e = parse_input("1|2\n2|3\n3|1\n\n1,2\n3,2,1\n").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "Line 6, column 1: rules form a cycle: 1 -> 2 -> 3 -> 1"
+        );
+    }
+}
@@ -0,0 +1,514 @@
+use log::debug;
+use std::collections::HashSet;
+use std::fmt;
+use std::io;
+
+use crate::geom::{Direction, UPoint};
+use crate::grid::Grid;
+use crate::parallel;
+use crate::progress::Progress;
+use crate::render::{Frames, Rgb};
+use crate::{Answer, AocError, Solution};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Walker;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        Walker::from_str(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input.clone()).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).len().into()
+    }
+}
+
+#[derive(Debug)]
+enum Turn {
+    Clockwise,
+    #[allow(dead_code)]
+    CounterClockwise,
+}
+
+#[derive(Debug, Clone)]
+pub struct Walker {
+    // two-dimensional grid, obstructions are a true value
+    grid: Grid<bool>,
+    pos: UPoint,
+    dir: Direction,
+}
+
+// Where the walker is and which way they're facing
+pub type State = (UPoint, Direction);
+
+// Everywhere the walker went, in order, starting with where they started
+#[derive(Debug)]
+pub struct Trajectory {
+    pub states: Vec<State>,
+    pub ending: Ending,
+}
+
+// How a walk ended
+#[derive(Debug, PartialEq, Eq)]
+pub enum Ending {
+    // Walked off the edge of the grid
+    Exited,
+    // Came back to a state they'd already been in, and would keep going round forever. The loop
+    // starts at this index in the states.
+    Looped { entry_index: usize },
+}
+
+impl Trajectory {
+    // All the distinct positions visited
+    fn positions(&self) -> HashSet<UPoint> {
+        self.states.iter().map(|&(pos, _)| pos).collect()
+    }
+}
+
+impl Walker {
+    // Parse an input string. The walker starts wherever the ^ is.
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let chars = Grid::parse(s, |c| matches!(c, '.' | '#' | '^').then_some(c))?;
+        let pos = chars
+            .find(|&c| c == '^')
+            .ok_or_else(|| AocError::expected(s, &s[s.len()..], "a ^ where the guard starts"))?;
+        let grid = chars.map(|&c| c == '#');
+
+        Ok(Walker {
+            grid,
+            pos,
+            dir: Direction::North,
+        })
+    }
+
+    // Turn 90 degrees in the given direction
+    fn turn(&mut self, t: Turn) {
+        debug!("Turning {:?} at {}", t, self.pos);
+        self.dir = match t {
+            Turn::Clockwise => self.dir.turn_right(),
+            Turn::CounterClockwise => self.dir.turn_left(),
+        };
+        debug!("Walker turned {:?}", self.dir);
+    }
+
+    // Walk until we leave the grid or end up going round in circles, turning right at every
+    // obstruction
+    fn walk(mut self) -> Trajectory {
+        let mut states = vec![(self.pos, self.dir)];
+        // When we were in each state, indexed by position and direction
+        let mut seen: Grid<[Option<usize>; 4]> = self.grid.map(|_| [None; 4]);
+        seen[self.pos][self.dir as usize] = Some(0);
+
+        loop {
+            match self.step() {
+                StepResult::Stepped => {}
+                StepResult::Obstructed => self.turn(Turn::Clockwise),
+                StepResult::OffGrid => {
+                    return Trajectory {
+                        states,
+                        ending: Ending::Exited,
+                    }
+                }
+            }
+
+            let seen_at = &mut seen[self.pos][self.dir as usize];
+            if let Some(entry_index) = *seen_at {
+                debug!("{self} looped back to step {entry_index}");
+                return Trajectory {
+                    states,
+                    ending: Ending::Looped { entry_index },
+                };
+            }
+            *seen_at = Some(states.len());
+            states.push((self.pos, self.dir));
+        }
+    }
+
+    // Take a step in the direction we're facing, and return the new position
+    fn step(&mut self) -> StepResult {
+        let Some(pos) = self.grid.offset(self.pos, self.dir.offset()) else {
+            return StepResult::OffGrid;
+        };
+
+        if self.grid[pos] {
+            return StepResult::Obstructed;
+        }
+
+        self.pos = pos;
+
+        StepResult::Stepped
+    }
+
+    // Draw the grid like the puzzle text does: # for obstructions, the path through `states` with
+    // | and -, or + where it turns or crosses itself, the walker at the last state, and extra
+    // obstructions as O over the top of everything else
+    fn draw(&self, states: &[State], obstructions: &[UPoint]) -> Grid<char> {
+        let mut canvas = self
+            .grid
+            .map(|&obstructed| if obstructed { '#' } else { '.' });
+        for &(pos, dir) in states {
+            let mark = match dir {
+                Direction::North | Direction::South => '|',
+                Direction::East | Direction::West => '-',
+            };
+            let cell = &mut canvas[pos];
+            *cell = match *cell {
+                '.' => mark,
+                c if c == mark => mark,
+                _ => '+',
+            };
+        }
+        if let Some(&(pos, dir)) = states.last() {
+            canvas[pos] = match dir {
+                Direction::North => '^',
+                Direction::East => '>',
+                Direction::South => 'v',
+                Direction::West => '<',
+            };
+        }
+        // Obstructions go on top, since one of them could be where the walker ends up
+        for &pos in obstructions {
+            canvas[pos] = 'O';
+        }
+        canvas
+    }
+}
+
+/// The guard's whole walk, with every spot where an obstruction would make them loop
+pub fn picture(walker: &Walker) -> Grid<char> {
+    let states = walker.clone().walk().states;
+    walker.draw(&states, &part2(walker))
+}
+
+/// Save a frame of the guard's walk every time they turn, and one at the end
+pub fn animate<P: Fn(char) -> Rgb>(walker: &Walker, frames: &mut Frames<P>) -> io::Result<()> {
+    let states = walker.clone().walk().states;
+    for i in 1..states.len() {
+        let turned = states[i].0 == states[i - 1].0;
+        if turned || i == states.len() - 1 {
+            frames.add(&walker.draw(&states[..=i], &[]))?;
+        }
+    }
+    Ok(())
+}
+
+/// Colours for drawing
+pub fn palette(c: char) -> Rgb {
+    match c {
+        '#' => Rgb(200, 200, 200),
+        '|' | '-' | '+' => Rgb(230, 180, 40),
+        '^' | '>' | 'v' | '<' => Rgb(230, 40, 40),
+        'O' => Rgb(60, 200, 80),
+        _ => Rgb(40, 40, 50),
+    }
+}
+
+impl fmt::Display for Walker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Walker @ {}", self.pos)?;
+        Ok(())
+    }
+}
+
+// The result of a step
+enum StepResult {
+    // Success, walker's new position
+    Stepped,
+    Obstructed,
+    OffGrid,
+}
+
+// Count up how many distinct positions the walker visits on their walk
+fn part1(walker: Walker) -> usize {
+    walker.walk().positions().len()
+}
+
+// Where could we add an obstruction and get the walker into a loop?
+fn part2(walker: &Walker) -> Vec<UPoint> {
+    // An obstruction anywhere the walker doesn't go wouldn't change anything, so only try spots on
+    // the original path, other than where the walker starts. They're tried in the order they're
+    // first visited so the answer always comes out the same, and each one is independent of the
+    // others so they can be spread across threads.
+    //
+    // Everything up until the walker first gets to a spot stays the same with an obstruction
+    // there, so the walk can pick up from the step before.
+    let states = walker.clone().walk().states;
+    let mut seen = HashSet::from([walker.pos]);
+    let candidates: Vec<(UPoint, State)> = states
+        .windows(2)
+        .filter(|pair| seen.insert(pair[1].0))
+        .map(|pair| (pair[1].0, pair[0]))
+        .collect();
+
+    let table = JumpTable::new(&walker.grid);
+    let progress = Progress::new("day6 obstructions", candidates.len() as u64);
+    let loops = parallel::map(&candidates, |&(obstruction, start)| {
+        let looped = table.loops(start, Some(obstruction));
+        progress.inc(1);
+        looped
+    });
+
+    candidates
+        .into_iter()
+        .zip(loops)
+        .filter_map(|((pos, _), looped)| looped.then_some(pos))
+        .collect()
+}
+
+// Where the obstructions are in each row and column, so the walker can go straight from one to the
+// next instead of a step at a time
+struct JumpTable {
+    // x of every obstruction in each row, in order
+    rows: Vec<Vec<usize>>,
+    // y of every obstruction in each column, in order
+    columns: Vec<Vec<usize>>,
+}
+
+impl JumpTable {
+    fn new(grid: &Grid<bool>) -> Self {
+        let mut rows = vec![Vec::new(); grid.height()];
+        let mut columns = vec![Vec::new(); grid.width()];
+        // Positions come out row by row, so both lists end up sorted
+        for pos in grid.positions(|&obstructed| obstructed) {
+            rows[pos.y].push(pos.x);
+            columns[pos.x].push(pos.y);
+        }
+        JumpTable { rows, columns }
+    }
+
+    // Where the walker stops going from `pos` in `dir`, which is right before the next
+    // obstruction. None if they walk off the grid instead. `extra` is one more obstruction which
+    // isn't in the table, so trying one out doesn't mean changing the table.
+    fn jump(&self, pos: UPoint, dir: Direction, extra: Option<UPoint>) -> Option<UPoint> {
+        // Obstructions in the same row or column, and where we are along it
+        let (line, at, extra) = match dir {
+            Direction::North | Direction::South => (
+                &self.columns[pos.x],
+                pos.y,
+                extra.filter(|e| e.x == pos.x).map(|e| e.y),
+            ),
+            Direction::East | Direction::West => (
+                &self.rows[pos.y],
+                pos.x,
+                extra.filter(|e| e.y == pos.y).map(|e| e.x),
+            ),
+        };
+
+        let stop = match dir {
+            Direction::North | Direction::West => {
+                let i = line.partition_point(|&o| o < at);
+                let before = i.checked_sub(1).map(|i| line[i]);
+                let extra = extra.filter(|&e| e < at);
+                [before, extra].into_iter().flatten().max()? + 1
+            }
+            Direction::South | Direction::East => {
+                let i = line.partition_point(|&o| o <= at);
+                let after = line.get(i).copied();
+                let extra = extra.filter(|&e| e > at);
+                [after, extra].into_iter().flatten().min()? - 1
+            }
+        };
+
+        Some(match dir {
+            Direction::North | Direction::South => UPoint::new(pos.x, stop),
+            Direction::East | Direction::West => UPoint::new(stop, pos.y),
+        })
+    }
+
+    // Whether the walker goes round in circles from the given state. Only the spots where they
+    // turn need remembering, since the walk between turns is always the same.
+    fn loops(&self, start: State, extra: Option<UPoint>) -> bool {
+        let (mut pos, mut dir) = start;
+        let mut turns = HashSet::new();
+        while let Some(stop) = self.jump(pos, dir, extra) {
+            pos = stop;
+            dir = dir.turn_right();
+            if !turns.insert((pos, dir)) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod day6_tests {
+    use super::*;
+    use crate::input;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    mod walker {
+        use super::*;
+
+        #[test]
+        fn test_parse() {
+            init();
+            let walker = Walker::from_str(&input::example(6).unwrap()).unwrap();
+            assert_eq!(walker.pos, UPoint::new(4, 6));
+            // Check for a couple of obstructions
+            assert!(!walker.grid[UPoint::new(0, 0)]);
+            assert!(!walker.grid[UPoint::new(3, 0)]);
+            assert!(walker.grid[UPoint::new(4, 0)]);
+            assert!(walker.grid[UPoint::new(2, 3)]);
+        }
+
+        #[test]
+        fn test_turn() {
+            init();
+            let mut walker = Walker::from_str(&input::example(6).unwrap()).unwrap();
+            // Should start facing north
+            assert_eq!(walker.dir, Direction::North);
+            // Turn clockwise should be facing East
+            walker.turn(Turn::Clockwise);
+            assert_eq!(walker.dir, Direction::East);
+            walker.turn(Turn::Clockwise);
+            assert_eq!(walker.dir, Direction::South);
+            // Try the other way
+            walker.turn(Turn::CounterClockwise);
+            assert_eq!(walker.dir, Direction::East);
+        }
+
+        #[test]
+        fn test_step() {
+            init();
+            let mut walker = Walker::from_str(&input::example(6).unwrap()).unwrap();
+            assert_eq!(walker.pos, UPoint::new(4, 6));
+            assert_eq!(walker.dir, Direction::North);
+
+            match walker.step() {
+                StepResult::Stepped => assert_eq!(walker.pos, UPoint::new(4, 5)),
+                _ => panic!("DOH"),
+            }
+
+            // Try walking until we hit an obstruction
+            while let StepResult::Stepped = walker.step() {}
+            assert_eq!(walker.pos, UPoint::new(4, 1));
+
+            walker.turn(Turn::Clockwise);
+            while let StepResult::Stepped = walker.step() {}
+            assert_eq!(walker.pos, UPoint::new(8, 1));
+        }
+    }
+
+    #[test]
+    fn test_part1() {
+        init();
+        let walker = Walker::from_str(&input::example(6).unwrap()).unwrap();
+        let result = part1(walker);
+        assert_eq!(result, 41);
+    }
+
+    #[test]
+    fn test_walk() {
+        init();
+        let walker = Walker::from_str(&input::example(6).unwrap()).unwrap();
+        let trajectory = walker.clone().walk();
+        assert_eq!(trajectory.ending, Ending::Exited);
+        assert_eq!(trajectory.states[0], (UPoint::new(4, 6), Direction::North));
+        // Turning in place at the first obstruction
+        assert_eq!(trajectory.states[5], (UPoint::new(4, 1), Direction::North));
+        assert_eq!(trajectory.states[6], (UPoint::new(4, 1), Direction::East));
+
+        let mut blocked = walker.clone();
+        blocked.grid[UPoint::new(3, 6)] = true;
+        let trajectory = blocked.walk();
+        let Ending::Looped { entry_index } = trajectory.ending else {
+            panic!("Should have looped");
+        };
+        // Right next to the start, so the loop goes back through where the walker started
+        assert_eq!(entry_index, 0);
+        assert!(trajectory.states.len() > 4);
+    }
+
+    #[test]
+    fn test_draw() {
+        init();
+        let walker = Walker::from_str(&input::example(6).unwrap()).unwrap();
+        let obstruction = UPoint::new(3, 6);
+        let mut blocked = walker.clone();
+        blocked.grid[obstruction] = true;
+        let states = blocked.walk().states;
+
+        // Just like the first loop in the puzzle text, except the walker has come back around
+        let expected = "\
+....#.....
+....+---+#
+....|...|.
+..#.|...|.
+....|..#|.
+....|...|.
+.#.O<---+.
+........#.
+#.........
+......#...
+";
+        assert_eq!(walker.draw(&states, &[obstruction]).to_string(), expected);
+
+        let picture = picture(&walker).to_string();
+        // One of the obstructions is the last spot the guard is in before leaving, and still shows
+        assert_eq!(picture.matches('O').count(), 6);
+        assert_eq!(picture.lines().last(), Some("......#O.."));
+    }
+
+    #[test]
+    fn test_jump() {
+        init();
+        let walker = Walker::from_str(&input::example(6).unwrap()).unwrap();
+        let table = JumpTable::new(&walker.grid);
+        let start = UPoint::new(4, 6);
+        assert_eq!(
+            table.jump(start, Direction::North, None),
+            Some(UPoint::new(4, 1))
+        );
+        assert_eq!(
+            table.jump(UPoint::new(4, 1), Direction::East, None),
+            Some(UPoint::new(8, 1))
+        );
+        assert_eq!(
+            table.jump(start, Direction::West, None),
+            Some(UPoint::new(2, 6))
+        );
+        assert_eq!(table.jump(start, Direction::South, None), None);
+        assert_eq!(table.jump(UPoint::new(4, 1), Direction::West, None), None);
+
+        // A temporary obstruction only counts if it's ahead of us
+        let extra = Some(UPoint::new(4, 3));
+        assert_eq!(
+            table.jump(start, Direction::North, extra),
+            Some(UPoint::new(4, 4))
+        );
+        let extra = Some(UPoint::new(4, 8));
+        assert_eq!(
+            table.jump(start, Direction::North, extra),
+            Some(UPoint::new(4, 1))
+        );
+        assert_eq!(
+            table.jump(start, Direction::South, extra),
+            Some(UPoint::new(4, 7))
+        );
+
+        assert!(!table.loops((start, Direction::North), None));
+        assert!(table.loops((start, Direction::North), Some(UPoint::new(3, 6))));
+    }
+
+    #[test]
+    fn test_part2() {
+        init();
+        let walker = Walker::from_str(&input::example(6).unwrap()).unwrap();
+        let mut result = part2(&walker);
+        assert_eq!(result.len(), 6);
+
+        result.sort_by_key(|pos| (pos.y, pos.x));
+        let expected = [(3, 6), (6, 7), (7, 7), (1, 8), (3, 8), (7, 9)];
+        let expected: Vec<UPoint> = expected.iter().map(|&(x, y)| UPoint::new(x, y)).collect();
+        assert_eq!(result, expected);
+    }
+}
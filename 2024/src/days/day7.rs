@@ -0,0 +1,355 @@
+use std::fmt;
+
+use log::debug;
+
+use crate::parallel;
+use crate::parse::Span;
+use crate::progress::Progress;
+use crate::{Answer, AocError, Solution};
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = ProblemInput;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
+pub type ProblemInput = Vec<(u64, Vec<u64>)>;
+
+// Something which can go between two numbers in an equation. Equations are solved right to left,
+// so it helps a lot if the operator can be undone, but that's optional.
+pub trait Operator: Copy + fmt::Display {
+    // `a op b`, or None if that's too big to fit
+    fn apply(&self, a: u64, b: u64) -> Option<u64>;
+
+    // What `a` had to be, given `b` and that `a op b` came out as `result`
+    fn undo(&self, _result: u64, _b: u64) -> Inverse {
+        Inverse::Unknown
+    }
+}
+
+// The result of undoing an operator
+#[derive(Debug, PartialEq, Eq)]
+pub enum Inverse {
+    Left(u64),
+    // Nothing on the left could have given that result
+    Impossible,
+    // The operator doesn't know how to work backwards, or there's more than one answer
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Oper {
+    Add,
+    Mul,
+    Concat,
+}
+
+impl Operator for Oper {
+    fn apply(&self, a: u64, b: u64) -> Option<u64> {
+        match self {
+            Oper::Add => a.checked_add(b),
+            Oper::Mul => a.checked_mul(b),
+            Oper::Concat => a.checked_mul(next_power_of_ten(b)?)?.checked_add(b),
+        }
+    }
+
+    fn undo(&self, result: u64, b: u64) -> Inverse {
+        match self {
+            Oper::Add if result >= b => Inverse::Left(result - b),
+            Oper::Mul if b == 0 && result == 0 => Inverse::Unknown,
+            Oper::Mul if b != 0 && result.is_multiple_of(b) => Inverse::Left(result / b),
+            Oper::Concat => {
+                // The result has to end with b's digits, and we strip them off
+                let Some(pow) = next_power_of_ten(b) else {
+                    return Inverse::Impossible;
+                };
+                if result >= b && (result - b).is_multiple_of(pow) {
+                    Inverse::Left((result - b) / pow)
+                } else {
+                    Inverse::Impossible
+                }
+            }
+            _ => Inverse::Impossible,
+        }
+    }
+}
+
+impl fmt::Display for Oper {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Oper::Add => write!(f, "+")?,
+            Oper::Mul => write!(f, "*")?,
+            Oper::Concat => write!(f, "||")?,
+        };
+        Ok(())
+    }
+}
+
+// The smallest power of ten bigger than n, which is what we shift by to stick n on the end of
+// another number. There isn't one for n with 20 digits.
+fn next_power_of_ten(n: u64) -> Option<u64> {
+    let mut pow: u64 = 10;
+    while pow <= n {
+        pow = pow.checked_mul(10)?;
+    }
+    Some(pow)
+}
+
+fn parse_input(s: &str) -> Result<ProblemInput, AocError> {
+    Span::new(s).map_lines(|line| line.record(":"))
+}
+
+fn part1(input: &ProblemInput) -> u64 {
+    calibration_result(input, &[Oper::Add, Oper::Mul])
+}
+
+fn part2(input: &ProblemInput) -> u64 {
+    calibration_result(input, &[Oper::Add, Oper::Mul, Oper::Concat])
+}
+
+fn calibration_result<O: Operator + Send + Sync>(input: &ProblemInput, operators: &[O]) -> u64 {
+    // Every line is independent, so they can be checked on different threads
+    let progress = Progress::new("day7 equations", input.len() as u64);
+    let results = parallel::map(input, |(value, numbers)| {
+        // One way of making it work is enough, and can be a lot quicker than finding all of them
+        let found = first_solution(*value, numbers, operators);
+        progress.inc(1);
+        let opers = found?;
+        debug!("This works: {value} = {}", equation(numbers, &opers));
+        Some(*value)
+    });
+
+    results.into_iter().flatten().sum()
+}
+
+// Write out numbers with operators between them, like "81 + 40 * 27"
+fn equation<O: Operator>(numbers: &[u64], opers: &[O]) -> String {
+    let mut s = numbers[0].to_string();
+    for (n, oper) in numbers[1..].iter().zip(opers) {
+        s += &format!(" {oper} {n}");
+    }
+    s
+}
+
+/// Every way of putting operators between the numbers to get the target value. Equations are
+/// always evaluated left to right. There can be a lot of them, like with a long run of 0s and 1s.
+pub fn solutions<O: Operator>(target: u64, numbers: &[u64], operators: &[O]) -> Vec<Vec<O>> {
+    let mut found = Vec::new();
+    search(target, numbers, operators, &mut Vec::new(), &mut |opers| {
+        found.push(opers);
+        true
+    });
+    found
+}
+
+// Any one way of putting operators between the numbers to get the target value, stopping as soon
+// as there is one
+fn first_solution<O: Operator>(target: u64, numbers: &[u64], operators: &[O]) -> Option<Vec<O>> {
+    let mut first = None;
+    search(target, numbers, operators, &mut Vec::new(), &mut |opers| {
+        first = Some(opers);
+        false
+    });
+    first
+}
+
+// Work backwards from the target by undoing the operator before the last number, which rules out
+// most operators straight away. `chosen` holds the operators picked so far, from right to left.
+// Each solution gets passed to `found`, which says whether to keep looking. Returns false once we
+// should stop.
+fn search<O: Operator>(
+    target: u64,
+    numbers: &[u64],
+    operators: &[O],
+    chosen: &mut Vec<O>,
+    found: &mut dyn FnMut(Vec<O>) -> bool,
+) -> bool {
+    let Some((&last, rest)) = numbers.split_last() else {
+        return true;
+    };
+    if rest.is_empty() {
+        if last == target {
+            return found(chosen.iter().rev().copied().collect());
+        }
+        return true;
+    }
+
+    for &oper in operators {
+        chosen.push(oper);
+        let keep_going = match oper.undo(target, last) {
+            Inverse::Left(left) => search(left, rest, operators, chosen, found),
+            Inverse::Impossible => true,
+            // No shortcut, so try everything the numbers on the left could come out as
+            Inverse::Unknown => {
+                let (&first, middle) = rest.split_first().unwrap();
+                evaluate(
+                    first,
+                    middle,
+                    operators,
+                    &mut Vec::new(),
+                    &mut |left, opers| {
+                        if oper.apply(left, last) != Some(target) {
+                            return true;
+                        }
+                        let mut opers = opers.to_vec();
+                        opers.extend(chosen.iter().rev());
+                        found(opers)
+                    },
+                )
+            }
+        };
+        chosen.pop();
+        if !keep_going {
+            return false;
+        }
+    }
+    true
+}
+
+// Go through everything the numbers can come out as going left to right from `value`, passing
+// each result and the operators to get there to `visit`, which says whether to keep going. Returns
+// false once we should stop.
+fn evaluate<O: Operator>(
+    value: u64,
+    numbers: &[u64],
+    operators: &[O],
+    chosen: &mut Vec<O>,
+    visit: &mut dyn FnMut(u64, &[O]) -> bool,
+) -> bool {
+    let Some((&n, rest)) = numbers.split_first() else {
+        return visit(value, chosen);
+    };
+
+    for &oper in operators {
+        let Some(next) = oper.apply(value, n) else {
+            continue;
+        };
+        chosen.push(oper);
+        let keep_going = evaluate(next, rest, operators, chosen, visit);
+        chosen.pop();
+        if !keep_going {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod day7_tests {
+    use super::*;
+    use crate::input;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[test]
+    fn test_part1() {
+        init();
+        let input = parse_input(&input::example(7).unwrap()).unwrap();
+        assert_eq!(part1(&input), 3749);
+    }
+
+    #[test]
+    fn test_part2() {
+        init();
+        let input = parse_input(&input::example(7).unwrap()).unwrap();
+        assert_eq!(part2(&input), 11387);
+    }
+
+    #[test]
+    fn test_solutions() {
+        init();
+        use Oper::*;
+        let all = [Add, Mul, Concat];
+        assert_eq!(
+            solutions(292, &[11, 6, 16, 20], &all),
+            vec![vec![Add, Mul, Add]]
+        );
+        // The last operator gets picked first
+        assert_eq!(
+            solutions(3267, &[81, 40, 27], &all),
+            vec![vec![Mul, Add], vec![Add, Mul]]
+        );
+        assert_eq!(solutions(156, &[15, 6], &all), vec![vec![Concat]]);
+        assert!(solutions(161011, &[16, 10, 13], &all).is_empty());
+        assert!(solutions(83, &[17, 5], &all).is_empty());
+        assert_eq!(equation(&[81, 40, 27], &[Add, Mul]), "81 + 40 * 27");
+
+        // Multiplying by zero can't be undone, so everything to the left gets tried
+        let mut zero = solutions(0, &[5, 3, 0], &all);
+        zero.sort_by_key(|opers| format!("{opers:?}"));
+        assert_eq!(
+            zero,
+            vec![vec![Add, Mul], vec![Concat, Mul], vec![Mul, Mul]]
+        );
+    }
+
+    #[test]
+    fn test_huge_numbers() {
+        init();
+        use Oper::*;
+        assert_eq!(next_power_of_ten(999), Some(1000));
+        assert_eq!(next_power_of_ten(u64::MAX), None);
+        assert_eq!(Concat.apply(1, u64::MAX - 1), None);
+        assert_eq!(Concat.undo(u64::MAX, u64::MAX - 1), Inverse::Impossible);
+        assert_eq!(
+            solutions(u64::MAX, &[1, u64::MAX - 1], &[Add, Mul, Concat]),
+            vec![vec![Add]]
+        );
+    }
+
+    #[test]
+    fn test_first_solution() {
+        init();
+        use Oper::*;
+        let all = [Add, Mul, Concat];
+        assert_eq!(solutions(0, &[0, 0, 0], &all).len(), 9);
+
+        // Every one of the 3^39 ways works, so this only finishes if it stops at the first
+        let zeros = [0; 40];
+        let opers = first_solution(0, &zeros, &all).unwrap();
+        assert_eq!(opers.len(), 39);
+        assert_eq!(
+            first_solution(292, &[11, 6, 16, 20], &all),
+            Some(vec![Add, Mul, Add])
+        );
+        assert_eq!(first_solution(83, &[17, 5], &all), None);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        init();
+        let e = parse_input("190: 10 19\n3267: 81 4O 27\n").unwrap_err();
+        assert!(matches!(
+            e,
+            AocError::Parse {
+                line: 2,
+                column: 10,
+                len: 2,
+                ..
+            }
+        ));
+        let e = parse_input("190: 10 19\n3267 81\n").unwrap_err();
+        assert!(matches!(
+            e,
+            AocError::Parse {
+                line: 2,
+                column: 1,
+                ..
+            }
+        ));
+    }
+}
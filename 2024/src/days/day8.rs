@@ -0,0 +1,237 @@
+use std::collections::{HashMap, HashSet};
+
+// use indicatif::ProgressIterator;
+use itertools::Itertools;
+use log::debug;
+
+use crate::geom::UPoint;
+use crate::{Answer, AocError, Solution};
+
+pub struct Day8;
+
+type Freq = char;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AntennaMap {
+    antennas: HashMap<Freq, HashSet<UPoint>>,
+    bounds: UPoint,
+}
+
+impl AntennaMap {
+    fn from_str(s: &str) -> Self {
+        let mut antennas: HashMap<Freq, HashSet<UPoint>> = HashMap::new();
+        let mut bounds = UPoint::new(0, 0);
+        for (y, line) in s.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                bounds = UPoint::new(x, y);
+                match c {
+                    '.' => continue,
+                    c => antennas.entry(c).or_default().insert(UPoint::new(x, y)),
+                };
+            }
+        }
+
+        Self { antennas, bounds }
+    }
+}
+
+impl Solution for Day8 {
+    type Input = AntennaMap;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        Ok(AntennaMap::from_str(input))
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(input).len().into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(input).len().into()
+    }
+}
+
+fn part1(map: &AntennaMap) -> HashSet<UPoint> {
+    let mut antinodes = HashSet::new();
+    for (freq, points) in &map.antennas {
+        debug!("Finding antinodes for {freq} {points:?}");
+        // iterate through all 2-combinations of points
+        // Get distance between A and B
+        // Antinodes are at (A - dist) and (B + dist) as long as they're within the bounds of
+        // the Map
+        for (&a, &b) in points.iter().tuple_combinations() {
+            debug!("  Comparing {a}, {b}");
+            let distance = b - a;
+            for point_option in [a - distance, b + distance] {
+                let Some(point) = point_option else { continue };
+                if point.x <= map.bounds.x && point.y <= map.bounds.y {
+                    debug!("    Antinode @ {point}");
+                    antinodes.insert(point);
+                } else {
+                    debug!("    Point {point} outside of range {}", map.bounds);
+                }
+            }
+        }
+    }
+    antinodes
+}
+
+// Like part 1, but antinodes extend forever away from the two antennas
+fn part2(map: &AntennaMap) -> HashSet<UPoint> {
+    let mut antinodes: HashSet<UPoint> = HashSet::new();
+    for (freq, points) in &map.antennas {
+        debug!("Finding antinodes for {freq} {points:?}");
+        for (&a, &b) in points.iter().tuple_combinations() {
+            debug!("  Comparing {a}, {b}");
+            let distance = b - a;
+            debug!("  Distance is {distance}");
+            // Walk in each direction off of A and B until we end up off the grid marking antinodes
+            // along the way.
+
+            let mut a2 = a;
+            while let Some(point) = a2 - distance {
+                a2 = point;
+                if point.x <= map.bounds.x && point.y <= map.bounds.y {
+                    debug!("    Antinode @ {point}");
+                    antinodes.insert(point);
+                } else {
+                    debug!("    Point {point} outside of range {}", map.bounds);
+                    break;
+                }
+            }
+            let mut a2 = a;
+            while let Some(point) = a2 + distance {
+                a2 = point;
+                if point.x <= map.bounds.x && point.y <= map.bounds.y {
+                    debug!("    Antinode @ {point}");
+                    antinodes.insert(point);
+                } else {
+                    debug!("    Point {point} outside of range {}", map.bounds);
+                    break;
+                }
+            }
+
+            let mut b2 = b;
+            while let Some(point) = b2 - distance {
+                b2 = point;
+                if point.x <= map.bounds.x && point.y <= map.bounds.y {
+                    debug!("    Antinode @ {point}");
+                    antinodes.insert(point);
+                } else {
+                    debug!("    Point {point} outside of range {}", map.bounds);
+                    break;
+                }
+            }
+            let mut b2 = b;
+            while let Some(point) = b2 + distance {
+                b2 = point;
+                if point.x <= map.bounds.x && point.y <= map.bounds.y {
+                    debug!("    Antinode @ {point}");
+                    antinodes.insert(point);
+                } else {
+                    debug!("    Point {point} outside of range {}", map.bounds);
+                    break;
+                }
+            }
+        }
+    }
+    antinodes
+}
+
+#[cfg(test)]
+mod day8_tests {
+    use super::*;
+    use crate::input;
+
+    fn init() -> AntennaMap {
+        let _ = env_logger::builder().is_test(true).try_init();
+        AntennaMap::from_str(&input::example(8).unwrap())
+    }
+
+    #[test]
+    fn test_map_from_str() {
+        let input = init();
+        assert_eq!(
+            input.antennas.get(&'0').unwrap(),
+            &HashSet::from([
+                UPoint::new(8, 1),
+                UPoint::new(5, 2),
+                UPoint::new(7, 3),
+                UPoint::new(4, 4)
+            ])
+        );
+        assert_eq!(
+            input.antennas.get(&'A').unwrap(),
+            &HashSet::from([UPoint::new(8, 8), UPoint::new(6, 5), UPoint::new(9, 9)])
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = init();
+        let mut antinodes: Vec<UPoint> = part1(&input).into_iter().collect();
+        antinodes.sort();
+        let expected = vec![
+            UPoint::new(0, 7),
+            UPoint::new(1, 5),
+            UPoint::new(2, 3),
+            UPoint::new(3, 1),
+            UPoint::new(3, 6),
+            UPoint::new(4, 2),
+            UPoint::new(6, 0),
+            UPoint::new(6, 5),
+            UPoint::new(7, 7),
+            UPoint::new(9, 4),
+            UPoint::new(10, 2),
+            UPoint::new(10, 10),
+            UPoint::new(10, 11),
+            UPoint::new(11, 0),
+        ];
+        assert_eq!(antinodes, expected);
+        assert_eq!(antinodes.len(), 14);
+    }
+
+    #[test]
+    fn test_part2() {
+        init();
+        let _ = init();
+        let input = AntennaMap::from_str(&input::example(8).unwrap());
+        let antinodes = part2(&input);
+
+        let mut expected: HashSet<UPoint> = (0..12).map(|i| UPoint::new(i, i)).collect();
+        expected = expected
+            .union(&HashSet::from([
+                UPoint::new(1, 0),
+                UPoint::new(1, 10),
+                UPoint::new(2, 3),
+                UPoint::new(2, 8),
+                UPoint::new(3, 1),
+                UPoint::new(3, 11),
+                UPoint::new(4, 2),
+                UPoint::new(6, 5),
+                UPoint::new(6, 0),
+                UPoint::new(9, 4),
+                UPoint::new(1, 5),
+                UPoint::new(11, 5),
+                UPoint::new(3, 6),
+                UPoint::new(0, 7),
+                UPoint::new(5, 7),
+                UPoint::new(4, 9),
+                UPoint::new(10, 2),
+                UPoint::new(10, 11),
+                UPoint::new(11, 0),
+                UPoint::new(8, 1),
+                UPoint::new(7, 3),
+                UPoint::new(5, 2),
+            ]))
+            .copied()
+            .collect();
+        assert_eq!(expected.len(), 34);
+        let extra = &antinodes - &expected;
+        let missing = &expected - &antinodes;
+        assert_eq!(extra, HashSet::new(), "Have extra antinodes");
+        assert_eq!(missing, HashSet::new(), "Missing some antinodes");
+
+        assert_eq!(antinodes.len(), 34);
+    }
+}
@@ -0,0 +1,553 @@
+use itertools::Itertools;
+use log::debug;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+use crate::interval::IntervalSet;
+use crate::parse::Span;
+use crate::{Answer, AocError, Solution};
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Disk;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        Disk::from_str(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        let mut disk = input.clone();
+        disk.defrag();
+        disk.checksum().into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let mut disk = input.clone();
+        disk.defrag_files();
+        disk.checksum().into()
+    }
+}
+
+// The files on a disk as runs of blocks, plus the free space in between. Nothing gets stored per
+// block, so a disk map with lots of 9s is no bigger than one with lots of 1s.
+#[derive(Clone)]
+pub struct Disk {
+    // Runs of blocks belonging to files, by where they start. A file is one extent until `defrag`
+    // splits it up.
+    extents: BTreeMap<usize, Extent>,
+    // Where the free space is. Between them, this and the extents cover every block.
+    free: IntervalSet<usize>,
+    // Size of the disk in blocks
+    size: usize,
+}
+
+// A run of blocks which all belong to the same file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Extent {
+    start: usize,
+    len: usize,
+    // ID of the file
+    owner: usize,
+}
+
+impl Extent {
+    fn end(&self) -> usize {
+        self.start + self.len
+    }
+
+    // Sum of each block's position multiplied by the file ID, which means adding up
+    // start..start+len without going through it
+    fn checksum(&self) -> usize {
+        self.owner * (self.len * self.start + self.len * self.len.saturating_sub(1) / 2)
+    }
+}
+
+impl Disk {
+    // Turn a string disk map into runs of file blocks and free space
+    fn from_str(s: &str) -> Result<Disk, AocError> {
+        let mut disk = Disk {
+            extents: BTreeMap::new(),
+            free: IntervalSet::new(),
+            size: 0,
+        };
+
+        let input = Span::new(s);
+        for (i, len) in input.of(s.trim()).digits()?.into_iter().enumerate() {
+            let len = len as usize;
+            // Files and free space take turns, starting with a file
+            if i % 2 == 0 {
+                disk.put(Extent {
+                    start: disk.size,
+                    len,
+                    owner: i / 2,
+                });
+            } else {
+                disk.free.insert(disk.size..disk.size + len);
+            }
+            disk.size += len;
+        }
+
+        Ok(disk)
+    }
+
+    // Each run of blocks on the disk in order, with the ID of the file it belongs to or None for
+    // free space
+    fn layout(&self) -> impl Iterator<Item = (Range<usize>, Option<usize>)> + '_ {
+        let files = self
+            .extents
+            .values()
+            .map(|e| (e.start..e.end(), Some(e.owner)));
+        let free = self.free.iter().map(|r| (r, None));
+        files
+            .merge_by(free, |(a, _), (b, _)| a.start < b.start)
+            // Pieces of the same file next to each other are one run as far as anybody else cares
+            .coalesce(|(a, x), (b, y)| {
+                if x == y {
+                    Ok((a.start..b.end, x))
+                } else {
+                    Err(((a, x), (b, y)))
+                }
+            })
+    }
+
+    // The file at a block, if there is one
+    fn owner(&self, block: usize) -> Option<usize> {
+        self.extents
+            .range(..=block)
+            .next_back()
+            .map(|(_, e)| e)
+            .filter(|e| block < e.end())
+            .map(|e| e.owner)
+    }
+
+    /// Draw the disk a block at a time, like the puzzle text does
+    pub fn render(&self, style: Style) -> String {
+        let mut s = String::new();
+        match style {
+            Style::Base36 => {
+                // Every block is as wide as the widest ID, so they all line up
+                let widest = self.extents.values().map(|e| e.owner).max().unwrap_or(0);
+                let width = base36(widest).len();
+                for (range, owner) in self.layout() {
+                    let block = match owner {
+                        Some(id) => format!("{:0>width$}", base36(id)),
+                        None => ".".repeat(width),
+                    };
+                    s.push_str(&block.repeat(range.len()));
+                }
+            }
+            Style::Separated => {
+                for (range, owner) in self.layout() {
+                    let block = match owner {
+                        Some(id) => id.to_string(),
+                        None => ".".to_string(),
+                    };
+                    for _ in range {
+                        if !s.is_empty() {
+                            s.push(' ');
+                        }
+                        s.push_str(&block);
+                    }
+                }
+            }
+        }
+        s
+    }
+
+    /// Turn the disk back into a disk map. The map doesn't say what the files' IDs are, so reading
+    /// it back numbers them in the order they're on the disk. Free space at the end gets left off,
+    /// like it is in the puzzle input.
+    pub fn to_map(&self) -> Result<String, MapError> {
+        let mut map = String::new();
+        let mut runs = self.layout().peekable();
+        while let Some((range, owner)) = runs.next() {
+            if owner.is_none() && runs.peek().is_none() {
+                break;
+            }
+            // Files and free space have to take turns, so two files right next to each other get
+            // no free space between them, and free space at the start gets an empty file before it
+            let is_file_next = map.len().is_multiple_of(2);
+            if owner.is_some() != is_file_next {
+                map.push('0');
+            }
+
+            let len = range.len();
+            if len > 9 {
+                return Err(MapError {
+                    start: range.start,
+                    len,
+                    free: owner.is_none(),
+                });
+            }
+            map.push_str(&len.to_string());
+        }
+        Ok(map)
+    }
+
+    /// Every run of blocks which belongs to something different on the other disk
+    pub fn diff(&self, other: &Disk) -> Vec<Change> {
+        // Nothing changes in between places where a run starts or ends on either disk
+        let edges: BTreeSet<usize> = self
+            .layout()
+            .chain(other.layout())
+            .flat_map(|(range, _)| [range.start, range.end])
+            .collect();
+
+        let mut changes: Vec<Change> = Vec::new();
+        for (&start, &end) in edges.iter().tuple_windows() {
+            let (before, after) = (self.owner(start), other.owner(start));
+            if before == after {
+                continue;
+            }
+            match changes.last_mut() {
+                Some(last)
+                    if last.blocks.end == start && (last.before, last.after) == (before, after) =>
+                {
+                    last.blocks.end = end;
+                }
+                _ => changes.push(Change {
+                    blocks: start..end,
+                    before,
+                    after,
+                }),
+            }
+        }
+        changes
+    }
+
+    // Defragment the data by moving all blocks from the end to the first available free space.
+    //
+    // Once it's done every file block is before `used`, so everything which starts out before
+    // there stays put, and the gaps get filled by whatever is after it, from the end backwards.
+    fn defrag(&mut self) {
+        let used = self.size - self.free.len();
+        let mut extents = std::mem::take(&mut self.extents);
+        let mut rear = extents.split_off(&used);
+
+        // A file running over the line gets cut in two, and its tail end gets moved
+        if let Some(mut last) = extents.last_entry() {
+            let file = last.get_mut();
+            if file.end() > used {
+                let tail = Extent {
+                    start: used,
+                    len: file.end() - used,
+                    owner: file.owner,
+                };
+                file.len -= tail.len;
+                rear.insert(used, tail);
+            }
+        }
+        self.extents = extents;
+
+        let gaps = self.free.split_off(used);
+        let gaps = std::mem::replace(&mut self.free, gaps);
+        let mut rear = rear.into_values().rev();
+        let mut moving = rear.next();
+        for gap in gaps.iter() {
+            let mut start = gap.start;
+            while start < gap.end {
+                let Some(file) = moving.as_mut() else {
+                    break;
+                };
+
+                // Take as much as fits from the end of the file
+                let len = file.len.min(gap.end - start);
+                debug!("Moving {len} blocks of file {} to {start}", file.owner);
+                self.put(Extent {
+                    start,
+                    len,
+                    owner: file.owner,
+                });
+                file.len -= len;
+                start += len;
+                if file.len == 0 {
+                    moving = rear.next();
+                }
+            }
+        }
+        self.free.insert(used..self.size);
+    }
+
+    // Defragment the data, but keep files contiguous. Again look for free space at the beginning,
+    // but look for files starting starting with the highest ID
+    fn defrag_files(&mut self) {
+        // Go through the files from highest ID to lowest, and through the free spaces from start
+        // to end, and move the file to the earliest free space
+        let mut files: Vec<Extent> = self.extents.values().copied().collect();
+        files.sort_by_key(|f| f.owner);
+        for file in files.into_iter().rev() {
+            // Only free space before the file is any good, moving it later doesn't help
+            let Some(space) = self.free.first_fit(file.len, 0..file.start) else {
+                continue;
+            };
+
+            debug!(
+                "Moving file id:{} from {} to {}",
+                file.owner, file.start, space.start
+            );
+            self.extents.remove(&file.start);
+            self.put(Extent {
+                start: space.start,
+                ..file
+            });
+            self.free.remove(space);
+            self.free.insert(file.start..file.end());
+        }
+    }
+
+    // Calculate the checksum, which is the sum of each block position multiplied by its ID
+    fn checksum(&self) -> usize {
+        self.extents.values().map(Extent::checksum).sum()
+    }
+
+    // Add an extent where there isn't one already. Files of length 0 don't take up anything.
+    fn put(&mut self, extent: Extent) {
+        if extent.len > 0 {
+            self.extents.insert(extent.start, extent);
+        }
+    }
+}
+
+impl fmt::Display for Disk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(Style::Base36))
+    }
+}
+
+/// How to draw each block of a disk
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Style {
+    /// File IDs in base 36, padded to the same width. With fewer than 36 files that's one
+    /// character per block, just like the puzzle text.
+    Base36,
+    /// File IDs in decimal, with a space between each block
+    Separated,
+}
+
+fn base36(mut n: usize) -> String {
+    let mut digits = Vec::new();
+    loop {
+        digits.push(std::char::from_digit((n % 36) as u32, 36).unwrap());
+        n /= 36;
+        if n == 0 {
+            break;
+        }
+    }
+    digits.iter().rev().collect()
+}
+
+/// A run of blocks which is too long to write as one digit of a disk map
+#[derive(Debug, PartialEq, Eq)]
+pub struct MapError {
+    pub start: usize,
+    pub len: usize,
+    /// Whether it's free space rather than a file
+    pub free: bool,
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let what = if self.free { "Free space" } else { "File" };
+        write!(
+            f,
+            "{what} at block {} is {} blocks long, which doesn't fit in one digit of a disk map",
+            self.start, self.len
+        )
+    }
+}
+
+impl Error for MapError {}
+
+/// Blocks which belong to one thing on one disk and something else on another. None is free space.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+    pub blocks: Range<usize>,
+    pub before: Option<usize>,
+    pub after: Option<usize>,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let show = |owner: Option<usize>| match owner {
+            Some(id) => format!("file {id}"),
+            None => "free".to_string(),
+        };
+        write!(
+            f,
+            "{}..{}: {} -> {}",
+            self.blocks.start,
+            self.blocks.end,
+            show(self.before),
+            show(self.after)
+        )
+    }
+}
+
+#[cfg(test)]
+mod day9_tests {
+    use super::*;
+
+    const TEST_DISK_MAP: &str = "2333133121414131402";
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    // Test expanding a disk map into a block layout
+    #[test]
+    fn test_from_str() {
+        init();
+        let d = Disk::from_str(TEST_DISK_MAP).unwrap();
+        let expected = "00...111...2...333.44.5555.6666.777.888899";
+        assert_eq!(d.to_string(), expected);
+
+        // Make sure the extents and free space are sane
+        let mut pos = 0;
+        for (range, _) in d.layout() {
+            assert_eq!(range.start, pos);
+            pos = range.end;
+        }
+        assert_eq!(pos, d.size);
+        assert_eq!(d.extents.len(), 10);
+        assert_eq!(d.free.len(), 14);
+    }
+
+    #[test]
+    fn test_defrag() {
+        init();
+        let mut d = Disk::from_str(TEST_DISK_MAP).unwrap();
+        let expected = "00...111...2...333.44.5555.6666.777.888899";
+        assert_eq!(d.to_string(), expected);
+
+        d.defrag();
+        let expected = "0099811188827773336446555566..............";
+        assert_eq!(d.to_string(), expected);
+    }
+
+    #[test]
+    fn test_checksum() {
+        init();
+        let mut d = Disk::from_str(TEST_DISK_MAP).unwrap();
+        d.defrag();
+        assert_eq!(d.checksum(), 1928);
+    }
+
+    #[test]
+    fn test_to_map() {
+        init();
+        let mut d = Disk::from_str(TEST_DISK_MAP).unwrap();
+        assert_eq!(d.to_map().unwrap(), TEST_DISK_MAP);
+
+        // Files which end up next to each other get a 0 between them, and the pieces of a file
+        // which got split up and put back together count as one
+        d.defrag();
+        assert_eq!(d.to_map().unwrap(), "2020103030103030102010402");
+
+        // Reading it back gives the same layout, just with the files renumbered in order
+        let mut d = Disk::from_str(TEST_DISK_MAP).unwrap();
+        d.defrag_files();
+        let map = d.to_map().unwrap();
+        assert_eq!(map, "2020103031213441454");
+        assert_eq!(
+            Disk::from_str(&map).unwrap().to_string(),
+            "00112333444.55.666....7777.8888.....9999"
+        );
+
+        // Moving the files out of the middle leaves one big gap which won't fit
+        let mut d = Disk::from_str("1525159").unwrap();
+        d.defrag_files();
+        let e = d.to_map().unwrap_err();
+        assert_eq!(
+            e,
+            MapError {
+                start: 4,
+                len: 15,
+                free: true
+            }
+        );
+        assert_eq!(
+            e.to_string(),
+            "Free space at block 4 is 15 blocks long, which doesn't fit in one digit of a disk map"
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        init();
+        let d = Disk::from_str(&"1".repeat(73)).unwrap();
+        assert!(d.to_string().starts_with("00..01..02.."));
+        assert!(d.to_string().ends_with("..0z..10"));
+        assert_eq!(d.render(Style::Separated).split(' ').nth(20), Some("10"));
+
+        let d = Disk::from_str("123").unwrap();
+        assert_eq!(d.render(Style::Separated), "0 . . 1 1 1");
+    }
+
+    #[test]
+    fn test_diff() {
+        init();
+        let before = Disk::from_str(TEST_DISK_MAP).unwrap();
+        let mut after = before.clone();
+        after.defrag_files();
+        assert!(before.diff(&before).is_empty());
+
+        let diff = before.diff(&after);
+        assert_eq!(
+            diff[..2],
+            [
+                Change {
+                    blocks: 2..4,
+                    before: None,
+                    after: Some(9)
+                },
+                Change {
+                    blocks: 4..5,
+                    before: None,
+                    after: Some(2)
+                }
+            ]
+        );
+        assert_eq!(diff[0].to_string(), "2..4: free -> file 9");
+        // Files 9, 2, 7 and 4 moved, so their old and new blocks all changed
+        let changed: usize = diff.iter().map(|c| c.blocks.len()).sum();
+        assert_eq!(changed, 2 * (2 + 1 + 3 + 2));
+    }
+
+    // Big files and gaps shouldn't cost any more than small ones
+    #[test]
+    fn test_extents() {
+        init();
+        let e = Extent {
+            start: 3,
+            len: 4,
+            owner: 2,
+        };
+        assert_eq!(e.checksum(), 2 * (3 + 4 + 5 + 6));
+
+        let mut d = Disk::from_str("919").unwrap();
+        d.defrag();
+        assert_eq!(d.to_string(), "000000000111111111.");
+        assert_eq!(d.free.iter().collect::<Vec<_>>(), vec![18..19]);
+
+        // A file which only partly fits, and one which doesn't fit at all
+        let mut d = Disk::from_str("12345").unwrap();
+        d.defrag();
+        assert_eq!(d.to_string(), "022111222......");
+        let mut d = Disk::from_str("12345").unwrap();
+        d.defrag_files();
+        assert_eq!(d.to_string(), "0..111....22222");
+    }
+
+    #[test]
+    fn test_file_defrag() {
+        init();
+        let mut d = Disk::from_str(TEST_DISK_MAP).unwrap();
+        d.defrag_files();
+        let expected = "00992111777.44.333....5555.6666.....8888..";
+        assert_eq!(d.to_string(), expected);
+        assert_eq!(d.checksum(), 2858);
+    }
+}
@@ -0,0 +1,35 @@
+use crate::Solver;
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+/// Every day that the runner knows about, in order
+pub const DAYS: &[(u8, &dyn Solver)] = &[
+    (1, &day1::Day1),
+    (2, &day2::Day2),
+    (3, &day3::Day3),
+    (4, &day4::Day4),
+    (5, &day5::Day5),
+    (6, &day6::Day6),
+    (7, &day7::Day7),
+    (8, &day8::Day8),
+    (9, &day9::Day9),
+    (10, &day10::Day10),
+    (11, &day11::Day11),
+    (12, &day12::Day12),
+];
+
+/// Look up the solver for a day
+pub fn get(day: u8) -> Option<&'static dyn Solver> {
+    DAYS.iter().find(|(d, _)| *d == day).map(|(_, s)| *s)
+}
@@ -0,0 +1,213 @@
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+use crate::input::InputError;
+
+/// Everything that can go wrong while reading and parsing puzzle input
+#[derive(Debug)]
+pub enum AocError {
+    /// Something in the input didn't make sense. Line and column numbers start at 1, and `len` is
+    /// how many characters of the line are at fault.
+    Parse {
+        line: usize,
+        column: usize,
+        len: usize,
+        // The whole line containing the problem
+        text: String,
+        kind: ParseErrorKind,
+    },
+    /// The input couldn't be read in the first place
+    Input(InputError),
+}
+
+/// What exactly was wrong with a bit of input
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidNumber(ParseIntError),
+    UnexpectedChar(char),
+    // A grid row was a different length than the first one
+    WrongLength { expected: usize, found: usize },
+    // Something was missing or malformed, and this describes what we wanted to find instead
+    Expected(String),
+    // It's all there and well formed, but doesn't make sense for some other reason
+    Invalid(String),
+}
+
+impl AocError {
+    /// Make a parse error about `part`, which should be a slice of `input`. The line and column get
+    /// worked out from where `part` sits in `input`, so callers can just hand over whatever they
+    /// got from `split()`, `lines()`, regex captures and so on. If `part` came from somewhere else
+    /// the error is about the whole input instead, since there's no telling where it goes.
+    pub fn parse(input: &str, part: &str, kind: ParseErrorKind) -> Self {
+        let whole = input.as_bytes().as_ptr_range();
+        let within = part.as_bytes().as_ptr_range();
+        let offset = if whole.start <= within.start && within.end <= whole.end {
+            within.start as usize - whole.start as usize
+        } else {
+            usize::MAX
+        };
+        let (part, offset) = if input.is_char_boundary(offset) {
+            (part, offset)
+        } else {
+            (input, 0)
+        };
+
+        let line_start = input[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = input[offset..]
+            .find('\n')
+            .map(|i| offset + i)
+            .unwrap_or(input.len());
+
+        AocError::Parse {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            len: part.chars().count(),
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            kind,
+        }
+    }
+
+    /// Shortcut for when something didn't parse as a number
+    pub fn number(input: &str, part: &str, e: ParseIntError) -> Self {
+        AocError::parse(input, part, ParseErrorKind::InvalidNumber(e))
+    }
+
+    /// Shortcut for when something was missing or malformed
+    pub fn expected(input: &str, part: &str, what: impl Into<String>) -> Self {
+        AocError::parse(input, part, ParseErrorKind::Expected(what.into()))
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::InvalidNumber(e) => write!(f, "invalid number ({e})"),
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "unexpected character '{c}'"),
+            ParseErrorKind::WrongLength { expected, found } => {
+                write!(f, "line is {found} characters long, expected {expected}")
+            }
+            ParseErrorKind::Expected(what) => write!(f, "expected {what}"),
+            ParseErrorKind::Invalid(why) => write!(f, "{why}"),
+        }
+    }
+}
+
+// The alternate form (`{:#}`) also prints the offending line with a caret underneath the bad part
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Parse {
+                line,
+                column,
+                len,
+                text,
+                kind,
+            } => {
+                write!(f, "Line {line}, column {column}: {kind}")?;
+                if f.alternate() {
+                    let gutter = line.to_string().len();
+                    writeln!(f)?;
+                    writeln!(f, "{:gutter$} |", "")?;
+                    writeln!(f, "{line} | {text}")?;
+                    write!(
+                        f,
+                        "{:gutter$} | {}{}",
+                        "",
+                        " ".repeat(column - 1),
+                        "^".repeat((*len).max(1))
+                    )?;
+                }
+                Ok(())
+            }
+            AocError::Input(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Parse {
+                kind: ParseErrorKind::InvalidNumber(e),
+                ..
+            } => Some(e),
+            AocError::Parse { .. } => None,
+            AocError::Input(e) => Some(e),
+        }
+    }
+}
+
+impl From<InputError> for AocError {
+    fn from(e: InputError) -> Self {
+        AocError::Input(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "1 2 3\n4 five 6\n";
+
+    #[test]
+    fn test_position() {
+        let bad = INPUT.split_whitespace().nth(4).unwrap();
+        let AocError::Parse {
+            line,
+            column,
+            len,
+            text,
+            ..
+        } = AocError::expected(INPUT, bad, "a number")
+        else {
+            panic!("Expected a parse error");
+        };
+        assert_eq!((line, column, len), (2, 3, 4));
+        assert_eq!(text, "4 five 6");
+    }
+
+    #[test]
+    fn test_not_in_input() {
+        let elsewhere = String::from("x");
+        let e = AocError::expected(INPUT, &elsewhere, "something");
+        assert!(matches!(
+            e,
+            AocError::Parse {
+                line: 1,
+                column: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_end_of_input() {
+        let e = AocError::expected(INPUT, &INPUT[INPUT.len()..], "more");
+        assert!(matches!(
+            e,
+            AocError::Parse {
+                line: 3,
+                column: 1,
+                len: 0,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_display() {
+        let bad = INPUT.split_whitespace().nth(4).unwrap();
+        let e = AocError::number(INPUT, bad, bad.parse::<u32>().unwrap_err());
+        assert_eq!(
+            e.to_string(),
+            "Line 2, column 3: invalid number (invalid digit found in string)"
+        );
+        assert_eq!(
+            format!("{e:#}"),
+            "Line 2, column 3: invalid number (invalid digit found in string)\n  |\n2 | 4 five 6\n  |   ^^^^"
+        );
+    }
+}
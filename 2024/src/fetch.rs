@@ -0,0 +1,313 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use log::{debug, info};
+use serde::Deserialize;
+
+use crate::input::{Source, INPUT_DIR_VAR};
+
+const YEAR: u16 = 2024;
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/nickpegg/advent_of_code/2024";
+
+/// Config file with the session token and such, relative to the package root. It holds a secret,
+/// so it's kept out of git.
+pub const CONFIG_FILE: &str = "aoc.toml";
+/// Environment variable with the session token, which takes priority over the config file
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable with the site to download from, which takes priority over the config file
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+/// Environment variable with contact details for the User-Agent, which takes priority over the
+/// config file
+pub const CONTACT_VAR: &str = "AOC_CONTACT";
+
+// Where we remember when the last request was made, in the input directory, so that throttling
+// works across runs too
+const LAST_FETCH_FILE: &str = ".last-fetch";
+
+/// Settings for downloading inputs
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The `session` cookie from a logged-in browser
+    pub session: Option<String>,
+    /// How the site can get in touch with whoever is downloading, like an email address. It goes
+    /// in the User-Agent.
+    pub contact: Option<String>,
+    pub base_url: String,
+    /// Minimum time between requests to the site
+    pub throttle_secs: u64,
+    /// Where inputs get saved, instead of `data/`
+    pub input_dir: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            contact: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            throttle_secs: 5,
+            input_dir: None,
+        }
+    }
+}
+
+impl Config {
+    /// Read the config file if there is one, then apply any environment variables on top
+    pub fn load(path: &Path) -> Result<Self, FetchError> {
+        let mut config = match fs::read_to_string(path) {
+            Ok(s) => toml::from_str(&s).map_err(|e| {
+                FetchError::Config(format!("Unable to parse {}: {e}", path.display()))
+            })?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(e) => return Err(FetchError::Io(path.to_path_buf(), e)),
+        };
+
+        if let Ok(session) = env::var(SESSION_VAR) {
+            config.session = Some(session);
+        }
+        if let Ok(contact) = env::var(CONTACT_VAR) {
+            config.contact = Some(contact);
+        }
+        if let Ok(url) = env::var(BASE_URL_VAR) {
+            config.base_url = url;
+        }
+        if let Some(dir) = env::var_os(INPUT_DIR_VAR) {
+            config.input_dir = Some(PathBuf::from(dir));
+        }
+        Ok(config)
+    }
+
+    /// What we tell the site we are, with the contact details if there are any
+    pub fn user_agent(&self) -> String {
+        match &self.contact {
+            Some(contact) => format!("{USER_AGENT} by {contact}"),
+            None => USER_AGENT.to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    Config(String),
+    NoSession,
+    Http(String, ureq::Error),
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::Config(msg) => write!(f, "{msg}"),
+            FetchError::NoSession => write!(
+                f,
+                "No session token, set {SESSION_VAR} or put session = \"...\" in {CONFIG_FILE}"
+            ),
+            FetchError::Http(url, e) => write!(f, "Unable to download {url}: {e}"),
+            FetchError::Io(path, e) => write!(f, "Unable to access {}: {e}", path.display()),
+        }
+    }
+}
+
+impl Error for FetchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchError::Config(_) | FetchError::NoSession => None,
+            FetchError::Http(_, e) => Some(e),
+            FetchError::Io(_, e) => Some(e),
+        }
+    }
+}
+
+/// Whether an input had to be downloaded
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    Cached(PathBuf),
+}
+
+/// Get a day's puzzle input into the input directory, unless it's already there. Files which
+/// exist but are empty (like the ones `aoc new` makes) don't count.
+pub fn fetch(day: u8, config: &Config) -> Result<Fetched, FetchError> {
+    let path = Source::Puzzle
+        .path(day, config.input_dir.as_deref())
+        .expect("Puzzle inputs always come from a file");
+    if fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+        debug!("Day {day} input is already at {}", path.display());
+        return Ok(Fetched::Cached(path));
+    }
+
+    let session = config.session.as_deref().ok_or(FetchError::NoSession)?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+    throttle(dir, Duration::from_secs(config.throttle_secs))?;
+
+    let url = format!(
+        "{}/{YEAR}/day/{day}/input",
+        config.base_url.trim_end_matches('/')
+    );
+    info!("Downloading {url}");
+    let result = ureq::get(&url)
+        .header("Cookie", &format!("session={session}"))
+        .header("User-Agent", &config.user_agent())
+        .call()
+        .and_then(|mut response| response.body_mut().read_to_string());
+    let body = result.map_err(|e| FetchError::Http(url, e))?;
+
+    fs::create_dir_all(dir).map_err(|e| FetchError::Io(dir.to_path_buf(), e))?;
+    fs::write(&path, body).map_err(|e| FetchError::Io(path.clone(), e))?;
+    Ok(Fetched::Downloaded(path))
+}
+
+// Wait until at least `interval` has passed since the last request, then note that we're about to
+// make another one
+fn throttle(dir: &Path, interval: Duration) -> Result<(), FetchError> {
+    let stamp = dir.join(LAST_FETCH_FILE);
+    let last = fs::read_to_string(&stamp)
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .map(|secs| UNIX_EPOCH + Duration::from_secs_f64(secs));
+
+    if let Some(last) = last {
+        let elapsed = SystemTime::now()
+            .duration_since(last)
+            .unwrap_or(Duration::ZERO);
+        if elapsed < interval {
+            let wait = interval - elapsed;
+            info!("Waiting {wait:.1?} before making another request");
+            thread::sleep(wait);
+        }
+    }
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    fs::create_dir_all(dir).map_err(|e| FetchError::Io(dir.to_path_buf(), e))?;
+    fs::write(&stamp, now.as_secs_f64().to_string()).map_err(|e| FetchError::Io(stamp, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::time::Instant;
+
+    // A tiny HTTP server which answers every request with `status` and `body`, and sends the
+    // request lines back over the channel
+    fn mock_server(status: &'static str, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = String::new();
+                let mut reader = BufReader::new(&stream);
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+                if tx.send(request).is_err() {
+                    break;
+                }
+            }
+        });
+
+        (url, rx)
+    }
+
+    fn test_config(name: &str, base_url: String) -> Config {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        Config {
+            session: Some("cookie".to_string()),
+            contact: None,
+            base_url,
+            throttle_secs: 0,
+            input_dir: Some(dir),
+        }
+    }
+
+    #[test]
+    fn test_fetch() {
+        let (url, requests) = mock_server("200 OK", "1 2 3\n");
+        let config = test_config("ok", url);
+        let path = config.input_dir.as_ref().unwrap().join("day6.txt");
+
+        assert_eq!(
+            fetch(6, &config).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2 3\n");
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2024/day/6/input "), "{request}");
+        assert!(
+            request.to_lowercase().contains("cookie: session=cookie"),
+            "{request}"
+        );
+        assert!(request.contains(&format!("{USER_AGENT}\r\n")), "{request}");
+
+        // The second time it comes from the cache
+        assert_eq!(fetch(6, &config).unwrap(), Fetched::Cached(path));
+        assert!(requests.try_recv().is_err());
+
+        fs::remove_dir_all(config.input_dir.unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_user_agent() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.user_agent(),
+            "github.com/nickpegg/advent_of_code/2024"
+        );
+        config.contact = Some("me@example.com".to_string());
+        assert_eq!(
+            config.user_agent(),
+            "github.com/nickpegg/advent_of_code/2024 by me@example.com"
+        );
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (url, _requests) = mock_server("400 Bad Request", "Please log in");
+        let mut config = test_config("errors", url);
+        assert!(matches!(
+            fetch(1, &config),
+            Err(FetchError::Http(_, ureq::Error::StatusCode(400)))
+        ));
+        // Nothing gets cached when the download fails
+        assert!(!config.input_dir.as_ref().unwrap().join("day1.txt").exists());
+
+        config.session = None;
+        assert!(matches!(fetch(1, &config), Err(FetchError::NoSession)));
+        let _ = fs::remove_dir_all(config.input_dir.unwrap());
+    }
+
+    #[test]
+    fn test_throttle() {
+        let dir = env::temp_dir().join(format!("aoc-throttle-{}", std::process::id()));
+        let interval = Duration::from_millis(200);
+        throttle(&dir, interval).unwrap();
+        let start = Instant::now();
+        throttle(&dir, interval).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
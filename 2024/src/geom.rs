@@ -0,0 +1,404 @@
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A point anywhere on the plane, including negative coordinates. Y grows downward, like the
+/// rows in puzzle input.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A point on a grid, which can't go negative. Moving one of these by a `Vector` is checked, and
+/// gives None if it would fall off the top or left edge.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UPoint {
+    pub x: usize,
+    pub y: usize,
+}
+
+/// The difference between two points
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    pub fn manhattan(&self, other: &Point) -> u64 {
+        (*other - *self).manhattan()
+    }
+
+    pub fn chebyshev(&self, other: &Point) -> u64 {
+        (*other - *self).chebyshev()
+    }
+
+    pub fn checked_add(self, v: Vector) -> Option<Point> {
+        Some(Point::new(
+            self.x.checked_add(v.x)?,
+            self.y.checked_add(v.y)?,
+        ))
+    }
+
+    pub fn checked_sub(self, v: Vector) -> Option<Point> {
+        Some(Point::new(
+            self.x.checked_sub(v.x)?,
+            self.y.checked_sub(v.y)?,
+        ))
+    }
+}
+
+impl UPoint {
+    pub const fn new(x: usize, y: usize) -> Self {
+        UPoint { x, y }
+    }
+
+    pub fn manhattan(&self, other: &UPoint) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(&self, other: &UPoint) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Returns true if `other` is next to this point in one of the 4 cardinal directions
+    pub fn is_adjacent(&self, other: &UPoint) -> bool {
+        self.manhattan(other) == 1
+    }
+
+    /// Move by a vector, or None if that would take us below zero
+    pub fn checked_add(self, v: Vector) -> Option<UPoint> {
+        let x = self.x.checked_add_signed(v.x.try_into().ok()?)?;
+        let y = self.y.checked_add_signed(v.y.try_into().ok()?)?;
+        Some(UPoint::new(x, y))
+    }
+
+    pub fn checked_sub(self, v: Vector) -> Option<UPoint> {
+        self.checked_add(-v)
+    }
+}
+
+impl Vector {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Vector { x, y }
+    }
+
+    /// Length when you can only move in the cardinal directions
+    pub fn manhattan(&self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    /// Length when diagonal moves count as one step, like a king in chess
+    pub fn chebyshev(&self) -> u64 {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, v: Vector) -> Point {
+        Point::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, v: Vector) -> Point {
+        Point::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add<Vector> for UPoint {
+    type Output = Option<UPoint>;
+
+    // Returns None if either X or Y would end up <0
+    fn add(self, v: Vector) -> Option<UPoint> {
+        self.checked_add(v)
+    }
+}
+
+impl Sub<Vector> for UPoint {
+    type Output = Option<UPoint>;
+
+    // Returns None if either X or Y would end up <0
+    fn sub(self, v: Vector) -> Option<UPoint> {
+        self.checked_sub(v)
+    }
+}
+
+impl Sub for UPoint {
+    type Output = Vector;
+
+    fn sub(self, other: UPoint) -> Vector {
+        Point::from(self) - Point::from(other)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, n: i64) -> Vector {
+        Vector::new(self.x * n, self.y * n)
+    }
+}
+
+impl From<UPoint> for Point {
+    fn from(p: UPoint) -> Point {
+        Point::new(p.x as i64, p.y as i64)
+    }
+}
+
+impl TryFrom<Point> for UPoint {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(p: Point) -> Result<UPoint, Self::Error> {
+        Ok(UPoint::new(p.x.try_into()?, p.y.try_into()?))
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl fmt::Display for UPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl fmt::Display for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+/// One of the 4 cardinal directions
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise from north
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Turn 90 degrees counter-clockwise
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    /// Turn 90 degrees clockwise
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    /// One step in this direction. North is negative y.
+    pub fn offset(self) -> Vector {
+        match self {
+            Direction::North => Vector::new(0, -1),
+            Direction::East => Vector::new(1, 0),
+            Direction::South => Vector::new(0, 1),
+            Direction::West => Vector::new(-1, 0),
+        }
+    }
+}
+
+/// One of the 4 cardinal or 4 ordinal (diagonal) directions
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// All directions, clockwise from north
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// Turn 90 degrees counter-clockwise
+    pub fn turn_left(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 6) % 8]
+    }
+
+    /// Turn 90 degrees clockwise
+    pub fn turn_right(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 2) % 8]
+    }
+
+    pub fn opposite(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+
+    /// One step in this direction. North is negative y.
+    pub fn offset(self) -> Vector {
+        match self {
+            Direction8::North => Vector::new(0, -1),
+            Direction8::NorthEast => Vector::new(1, -1),
+            Direction8::East => Vector::new(1, 0),
+            Direction8::SouthEast => Vector::new(1, 1),
+            Direction8::South => Vector::new(0, 1),
+            Direction8::SouthWest => Vector::new(-1, 1),
+            Direction8::West => Vector::new(-1, 0),
+            Direction8::NorthWest => Vector::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(d: Direction) -> Direction8 {
+        Direction8::ALL[d as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upoint_checked_math() {
+        let p = UPoint::new(2, 3);
+        assert_eq!(p + Vector::new(1, -3), Some(UPoint::new(3, 0)));
+        assert_eq!(p + Vector::new(-3, 0), None);
+        assert_eq!(p - Vector::new(2, 2), Some(UPoint::new(0, 1)));
+        assert_eq!(p - Vector::new(0, 4), None);
+        assert_eq!(UPoint::new(5, 1) - p, Vector::new(3, -2));
+    }
+
+    #[test]
+    fn test_point_math() {
+        let p = Point::new(2, 3);
+        assert_eq!(p + Vector::new(-5, 1), Point::new(-3, 4));
+        assert_eq!(p - Point::new(5, 5), Vector::new(-3, -2));
+        assert_eq!(p.checked_add(Vector::new(i64::MAX, 0)), None);
+        assert_eq!(Vector::new(1, -2) * 3, Vector::new(3, -6));
+        assert_eq!(-Vector::new(1, -2), Vector::new(-1, 2));
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Point::from(UPoint::new(1, 2)), Point::new(1, 2));
+        assert_eq!(UPoint::try_from(Point::new(1, 2)), Ok(UPoint::new(1, 2)));
+        assert!(UPoint::try_from(Point::new(-1, 2)).is_err());
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(1, 1);
+        let b = Point::new(4, -3);
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(UPoint::new(9, 9).manhattan(&UPoint::new(8, 7)), 3);
+        assert_eq!(UPoint::new(9, 9).chebyshev(&UPoint::new(8, 7)), 2);
+    }
+
+    #[test]
+    fn test_is_adjacent() {
+        assert!(UPoint::new(1, 1).is_adjacent(&UPoint::new(1, 2)));
+        assert!(!UPoint::new(1, 1).is_adjacent(&UPoint::new(2, 2)));
+        assert!(UPoint::new(9, 9).is_adjacent(&UPoint::new(8, 9)));
+        assert!(!UPoint::new(9, 9).is_adjacent(&UPoint::new(9, 9)));
+    }
+
+    #[test]
+    fn test_ord() {
+        // Ordered by x, then y, and consistent with Eq
+        let mut points = vec![
+            UPoint::new(1, 0),
+            UPoint::new(0, 5),
+            UPoint::new(1, 0),
+            UPoint::new(0, 1),
+        ];
+        points.sort();
+        points.dedup();
+        assert_eq!(
+            points,
+            vec![UPoint::new(0, 1), UPoint::new(0, 5), UPoint::new(1, 0)]
+        );
+    }
+
+    #[test]
+    fn test_direction_turns() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::South.opposite(), Direction::North);
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.offset() + d.opposite().offset(), Vector::default());
+        }
+
+        assert_eq!(Direction8::NorthEast.turn_right(), Direction8::SouthEast);
+        assert_eq!(Direction8::North.turn_left(), Direction8::West);
+        assert_eq!(Direction8::SouthWest.opposite(), Direction8::NorthEast);
+        for d in Direction8::ALL {
+            assert_eq!(d.offset() + d.opposite().offset(), Vector::default());
+        }
+        assert_eq!(Direction8::from(Direction::West), Direction8::West);
+    }
+}
@@ -0,0 +1,311 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::geom::{Direction, Direction8, UPoint, Vector};
+use crate::{AocError, ParseErrorKind};
+
+/// A rectangular two-dimensional grid, stored row by row
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Make a grid filled with copies of a value
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parse a grid from text, one row per line, mapping each character to a cell. The mapping
+    /// function returns None for characters it doesn't understand, which is an error.
+    pub fn parse(s: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self, AocError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for line in s.lines() {
+            let mut found = 0;
+            for (i, c) in line.char_indices() {
+                let cell = f(c).ok_or_else(|| {
+                    let bad = &line[i..i + c.len_utf8()];
+                    AocError::parse(s, bad, ParseErrorKind::UnexpectedChar(c))
+                })?;
+                cells.push(cell);
+                found += 1;
+            }
+
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                let kind = ParseErrorKind::WrongLength { expected, found };
+                return Err(AocError::parse(s, line, kind));
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, pos: UPoint) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn get(&self, pos: UPoint) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: UPoint) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Move from a position by an offset. Returns None if that would go off the grid.
+    pub fn offset(&self, pos: UPoint, offset: Vector) -> Option<UPoint> {
+        (pos + offset).filter(|&p| self.in_bounds(p))
+    }
+
+    /// All cells along with their positions, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (UPoint, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (UPoint::new(i % self.width, i / self.width), cell))
+    }
+
+    /// The cells in row `y`, left to right
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        let start = (y * self.width).min(self.cells.len());
+        let end = if y < self.height {
+            start + self.width
+        } else {
+            start
+        };
+        self.cells[start..end].iter()
+    }
+
+    /// The cells in column `x`, top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).map(move |y| &self[UPoint::new(x, y)])
+    }
+
+    /// Walk in a straight line from `start` (inclusive) by `step` until we fall off the grid.
+    /// Diagonal steps give diagonals.
+    pub fn ray(&self, start: UPoint, step: Vector) -> impl Iterator<Item = (UPoint, &T)> {
+        let first = self.in_bounds(start).then_some(start);
+        std::iter::successors(first, move |&pos| self.offset(pos, step))
+            .map(|pos| (pos, &self[pos]))
+    }
+
+    /// The 4 cardinal neighbours of a position which are on the grid
+    pub fn neighbours4(&self, pos: UPoint) -> impl Iterator<Item = (UPoint, &T)> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d.offset()))
+            .map(|p| (p, &self[p]))
+    }
+
+    /// All 8 neighbours of a position, including diagonals, which are on the grid
+    pub fn neighbours8(&self, pos: UPoint) -> impl Iterator<Item = (UPoint, &T)> {
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d.offset()))
+            .map(|p| (p, &self[p]))
+    }
+
+    /// Position of the first cell, row by row, that matches
+    pub fn find(&self, pred: impl Fn(&T) -> bool) -> Option<UPoint> {
+        self.iter().find(|(_, cell)| pred(cell)).map(|(pos, _)| pos)
+    }
+
+    /// Positions of all cells that match, row by row
+    pub fn positions<'a>(
+        &'a self,
+        pred: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = UPoint> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| pred(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// Make a new grid of the same size by transforming each cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    fn index_of(&self, pos: UPoint) -> Option<usize> {
+        self.in_bounds(pos).then_some(pos.y * self.width + pos.x)
+    }
+}
+
+impl<T> Index<UPoint> for Grid<T> {
+    type Output = T;
+
+    // Panics if the position is off the grid
+    fn index(&self, pos: UPoint) -> &T {
+        match self.index_of(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "Position {pos} is outside of the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<UPoint> for Grid<T> {
+    fn index_mut(&mut self, pos: UPoint) -> &mut T {
+        match self.index_of(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "Position {pos} is outside of the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height {
+            for cell in self.row(y) {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Deliberately not square, to catch mixed up rows and columns
+    const TEST_GRID: &str = "abcd\nefgh\nijkl\n";
+
+    fn test_grid() -> Grid<char> {
+        Grid::parse(TEST_GRID, Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let g = test_grid();
+        assert_eq!(g.width(), 4);
+        assert_eq!(g.height(), 3);
+        assert_eq!(g[UPoint::new(0, 0)], 'a');
+        assert_eq!(g[UPoint::new(3, 0)], 'd');
+        assert_eq!(g[UPoint::new(1, 2)], 'j');
+        assert_eq!(g.get(UPoint::new(4, 0)), None);
+        assert_eq!(g.get(UPoint::new(0, 3)), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = Grid::parse("ab\nabc\n", Some).unwrap_err();
+        assert!(matches!(
+            e,
+            AocError::Parse {
+                line: 2,
+                column: 1,
+                kind: ParseErrorKind::WrongLength {
+                    expected: 2,
+                    found: 3
+                },
+                ..
+            }
+        ));
+        let e = Grid::parse("12\n3x\n", |c| c.to_digit(10)).unwrap_err();
+        assert!(matches!(
+            e,
+            AocError::Parse {
+                line: 2,
+                column: 2,
+                len: 1,
+                kind: ParseErrorKind::UnexpectedChar('x'),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let mut g = test_grid();
+        *g.get_mut(UPoint::new(2, 1)).unwrap() = 'G';
+        g[UPoint::new(0, 2)] = 'I';
+        assert_eq!(g.to_string(), "abcd\nefGh\nIjkl\n");
+        assert_eq!(g.get_mut(UPoint::new(9, 9)), None);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let g = test_grid();
+        assert_eq!(g.row(1).collect::<String>(), "efgh");
+        assert_eq!(g.column(3).collect::<String>(), "dhl");
+        assert_eq!(g.row(3).count(), 0);
+        assert_eq!(g.column(4).count(), 0);
+    }
+
+    #[test]
+    fn test_ray() {
+        let g = test_grid();
+        let diagonal: String = g
+            .ray(UPoint::new(0, 0), Vector::new(1, 1))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(diagonal, "afk");
+        let backwards: String = g
+            .ray(UPoint::new(3, 2), Vector::new(-1, 0))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(backwards, "lkji");
+        assert_eq!(g.ray(UPoint::new(5, 5), Vector::new(1, 0)).count(), 0);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let g = test_grid();
+        let n: String = g.neighbours4(UPoint::new(0, 0)).map(|(_, c)| c).collect();
+        assert_eq!(n, "be");
+        let n: String = g.neighbours4(UPoint::new(1, 1)).map(|(_, c)| c).collect();
+        assert_eq!(n, "bgje");
+        let n: String = g.neighbours8(UPoint::new(3, 2)).map(|(_, c)| c).collect();
+        assert_eq!(n, "hkg");
+        assert_eq!(g.neighbours8(UPoint::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_find() {
+        let g = test_grid();
+        assert_eq!(g.find(|&c| c == 'g'), Some(UPoint::new(2, 1)));
+        assert_eq!(g.find(|&c| c == 'z'), None);
+        let vowels: Vec<UPoint> = g.positions(|c| "aeiou".contains(*c)).collect();
+        assert_eq!(
+            vowels,
+            vec![UPoint::new(0, 0), UPoint::new(0, 1), UPoint::new(0, 2)]
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Environment variable pointing at a directory of inputs to use instead of `data/`
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+const DEFAULT_INPUT_DIR: &str = "data";
+
+/// Where to read a day's puzzle input from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// The real puzzle input, dayN.txt
+    Puzzle,
+    /// The example input from the puzzle text, dayN_test.txt
+    Example,
+    /// A specific file
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// Figure out the source from an `--input` argument, where `-` means stdin. Without one, use
+    /// the example or real puzzle input.
+    pub fn from_arg(arg: Option<&str>, example: bool) -> Self {
+        match arg {
+            Some("-") => Source::Stdin,
+            Some(path) => Source::File(PathBuf::from(path)),
+            None if example => Source::Example,
+            None => Source::Puzzle,
+        }
+    }
+
+    /// The file this source reads from, or None for stdin. `input_dir` overrides the default
+    /// `data/` directory for puzzle and example inputs.
+    pub fn path(&self, day: u8, input_dir: Option<&Path>) -> Option<PathBuf> {
+        let dir = input_dir.unwrap_or(Path::new(DEFAULT_INPUT_DIR));
+        match self {
+            Source::Puzzle => Some(dir.join(format!("day{day}.txt"))),
+            Source::Example => Some(dir.join(format!("day{day}_test.txt"))),
+            Source::File(path) => Some(path.clone()),
+            Source::Stdin => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    day: u8,
+    // None if we were reading from stdin
+    path: Option<PathBuf>,
+    source: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let from = match &self.path {
+            Some(path) => path.display().to_string(),
+            None => "stdin".to_string(),
+        };
+        write!(
+            f,
+            "Unable to read input for day {} from {from}: {}",
+            self.day, self.source
+        )?;
+        if self.source.kind() == io::ErrorKind::NotFound {
+            write!(
+                f,
+                " (download it there, point {INPUT_DIR_VAR} at another directory, or use --input)"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Read a day's input from the given source. Puzzle and example inputs come from the
+/// `AOC_INPUT_DIR` directory if it's set, or `data/` otherwise.
+pub fn load(day: u8, source: &Source) -> Result<String, InputError> {
+    let input_dir = env::var_os(INPUT_DIR_VAR).map(PathBuf::from);
+    let path = source.path(day, input_dir.as_deref());
+
+    let result = match &path {
+        Some(path) => fs::read_to_string(path),
+        None => {
+            let mut s = String::new();
+            io::stdin().read_to_string(&mut s).map(|_| s)
+        }
+    };
+
+    result.map_err(|source| InputError { day, path, source })
+}
+
+/// Shortcut for loading a day's example input, mostly for tests
+pub fn example(day: u8) -> Result<String, InputError> {
+    load(day, &Source::Example)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(Source::from_arg(None, false), Source::Puzzle);
+        assert_eq!(Source::from_arg(None, true), Source::Example);
+        assert_eq!(Source::from_arg(Some("-"), false), Source::Stdin);
+        assert_eq!(
+            Source::from_arg(Some("foo.txt"), true),
+            Source::File(PathBuf::from("foo.txt"))
+        );
+    }
+
+    #[test]
+    fn test_path() {
+        assert_eq!(
+            Source::Puzzle.path(6, None),
+            Some(PathBuf::from("data/day6.txt"))
+        );
+        assert_eq!(
+            Source::Example.path(6, None),
+            Some(PathBuf::from("data/day6_test.txt"))
+        );
+        assert_eq!(
+            Source::Example.path(6, Some(Path::new("/tmp/inputs"))),
+            Some(PathBuf::from("/tmp/inputs/day6_test.txt"))
+        );
+        // An explicit file ignores the input directory
+        assert_eq!(
+            Source::File(PathBuf::from("x.txt")).path(6, Some(Path::new("/tmp"))),
+            Some(PathBuf::from("x.txt"))
+        );
+        assert_eq!(Source::Stdin.path(6, None), None);
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let err = load(6, &Source::File(PathBuf::from("data/nope.txt"))).unwrap_err();
+        assert!(err.to_string().contains("data/nope.txt"), "{err}");
+        assert!(err.to_string().contains("--input"), "{err}");
+    }
+
+    #[test]
+    fn test_example() {
+        assert!(example(4).unwrap().starts_with("MMMSXXMASM"));
+    }
+}
@@ -0,0 +1,335 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::ops::{Add, Range, Sub};
+
+/// Integers which can be the ends of a range in an IntervalSet
+pub trait Int: Copy + Ord + fmt::Debug + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {
+        $(impl Int for $t {
+            const ZERO: Self = 0;
+        })*
+    };
+}
+
+impl_int!(usize, u32, u64, i32, i64);
+
+/// A set of integers stored as sorted, non-overlapping half-open ranges. Ranges which overlap or
+/// touch get merged together as they're inserted, so e.g. inserting 0..3 and 3..5 leaves just 0..5.
+/// Looking up a single point is logarithmic in the number of ranges, and so is finding the first
+/// range big enough to hold something, for each different length of range there is.
+#[derive(Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    // start -> end of each range
+    ranges: BTreeMap<T, T>,
+    // length -> starts of the ranges that long, for finding one big enough without looking at
+    // every range
+    by_len: BTreeMap<T, BTreeSet<T>>,
+    // Sum of the lengths of all the ranges
+    total: T,
+}
+
+impl<T: Int> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            ranges: BTreeMap::new(),
+            by_len: BTreeMap::new(),
+            total: T::ZERO,
+        }
+    }
+}
+
+impl<T: Int> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a range, merging it with any ranges it overlaps or touches
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let (mut start, mut end) = (range.start, range.end);
+
+        // Something starting before us and running into us
+        if let Some((&s, &e)) = self.ranges.range(..=start).next_back() {
+            if e >= start {
+                self.take(s);
+                start = s;
+                end = end.max(e);
+            }
+        }
+        // Anything starting inside of us, or right where we end
+        let covered: Vec<T> = self.ranges.range(start..=end).map(|(&s, _)| s).collect();
+        for s in covered {
+            end = end.max(self.take(s));
+        }
+
+        self.put(start, end);
+    }
+
+    /// Take a range out of the set, splitting any ranges which stick out of either end of it
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        if let Some((&s, &e)) = self.ranges.range(..range.start).next_back() {
+            if e > range.start {
+                self.take(s);
+                self.put(s, range.start);
+                if e > range.end {
+                    self.put(range.end, e);
+                }
+            }
+        }
+        let covered: Vec<T> = self.ranges.range(range.clone()).map(|(&s, _)| s).collect();
+        for s in covered {
+            let e = self.take(s);
+            if e > range.end {
+                self.put(range.end, e);
+            }
+        }
+    }
+
+    /// Add every range from another set
+    pub fn merge(&mut self, other: &IntervalSet<T>) {
+        for range in other.iter() {
+            self.insert(range);
+        }
+    }
+
+    /// Split the set in two at `at`, leaving everything before it here and returning the rest
+    pub fn split_off(&mut self, at: T) -> IntervalSet<T> {
+        let mut rest = IntervalSet::new();
+        if let Some(range) = self.get(at) {
+            if range.start < at {
+                self.take(range.start);
+                self.put(range.start, at);
+                self.put(at, range.end);
+            }
+        }
+        let moved: Vec<T> = self.ranges.range(at..).map(|(&s, _)| s).collect();
+        for s in moved {
+            let e = self.take(s);
+            rest.put(s, e);
+        }
+        rest
+    }
+
+    /// The range which contains `point`, if there is one
+    pub fn get(&self, point: T) -> Option<Range<T>> {
+        self.ranges
+            .range(..=point)
+            .next_back()
+            .filter(|(_, &e)| point < e)
+            .map(|(&s, &e)| s..e)
+    }
+
+    pub fn contains(&self, point: T) -> bool {
+        self.get(point).is_some()
+    }
+
+    /// The earliest `len` long stretch which is entirely in the set and inside `within`. When the
+    /// set holds free space, that's the first gap big enough to fit something.
+    pub fn first_fit(&self, len: T, within: Range<T>) -> Option<Range<T>> {
+        if within.is_empty() {
+            return None;
+        }
+        let fits = |start: T| start + len <= within.end;
+
+        // A range sticking out of the start of the window is further left than anything else
+        if let Some(range) = self.get(within.start) {
+            if range.start < within.start && fits(within.start) && within.start + len <= range.end {
+                return Some(within.start..within.start + len);
+            }
+        }
+
+        // Otherwise it's the earliest start inside the window out of every length which is long
+        // enough. Only the first of each length can be it, since any later one would run past the
+        // end of the window if that one does.
+        self.by_len
+            .range(len..)
+            .filter_map(|(_, starts)| starts.range(within.start..).next())
+            .copied()
+            .filter(|&start| start < within.end && fits(start))
+            .min()
+            .map(|start| start..start + len)
+    }
+
+    /// The ranges in the set, in order
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().map(|(&s, &e)| s..e)
+    }
+
+    /// How many integers are in the set
+    pub fn len(&self) -> T {
+        self.total
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// How many separate ranges the set is made of
+    pub fn ranges(&self) -> usize {
+        self.ranges.len()
+    }
+
+    // Add a range which doesn't overlap anything
+    fn put(&mut self, start: T, end: T) {
+        self.ranges.insert(start, end);
+        self.by_len.entry(end - start).or_default().insert(start);
+        self.total = self.total + (end - start);
+    }
+
+    // Remove the range starting at `start`, returning where it ended
+    fn take(&mut self, start: T) -> T {
+        let end = self.ranges.remove(&start).expect("No range starts there");
+        let starts = self.by_len.get_mut(&(end - start)).unwrap();
+        starts.remove(&start);
+        if starts.is_empty() {
+            self.by_len.remove(&(end - start));
+        }
+        self.total = self.total - (end - start);
+        end
+    }
+}
+
+impl<T: Int> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T: Int> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges<T: Int>(set: &IntervalSet<T>) -> Vec<Range<T>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut set: IntervalSet<usize> = [0..3, 3..5, 10..12, 7..8].into_iter().collect();
+        assert_eq!(ranges(&set), vec![0..5, 7..8, 10..12]);
+        assert_eq!(set.len(), 8);
+
+        // Bridge a couple of them
+        set.insert(6..11);
+        assert_eq!(ranges(&set), vec![0..5, 6..12]);
+        assert_eq!(set.len(), 11);
+
+        // Already covered, or empty
+        set.insert(1..4);
+        set.insert(20..20);
+        assert_eq!(ranges(&set), vec![0..5, 6..12]);
+        assert_eq!(set.ranges(), 2);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut set: IntervalSet<i64> = [-10..10, 20..30].into_iter().collect();
+        set.remove(-2..2);
+        assert_eq!(ranges(&set), vec![-10..-2, 2..10, 20..30]);
+        set.remove(5..25);
+        assert_eq!(ranges(&set), vec![-10..-2, 2..5, 25..30]);
+        set.remove(-100..-5);
+        assert_eq!(ranges(&set), vec![-5..-2, 2..5, 25..30]);
+        assert_eq!(set.len(), 11);
+
+        set.remove(-5..100);
+        assert!(set.is_empty());
+        assert_eq!(set.len(), 0);
+    }
+
+    #[test]
+    fn test_lookup() {
+        let set: IntervalSet<usize> = [2..4, 8..12].into_iter().collect();
+        assert_eq!(set.get(2), Some(2..4));
+        assert_eq!(set.get(11), Some(8..12));
+        assert_eq!(set.get(4), None);
+        assert_eq!(set.get(0), None);
+        assert!(set.contains(3));
+        assert!(!set.contains(12));
+    }
+
+    #[test]
+    fn test_first_fit() {
+        let set: IntervalSet<usize> = [2..4, 5..6, 8..12].into_iter().collect();
+        assert_eq!(set.first_fit(1, 0..100), Some(2..3));
+        assert_eq!(set.first_fit(3, 0..100), Some(8..11));
+        assert_eq!(set.first_fit(5, 0..100), None);
+        // Only the part inside the window counts
+        assert_eq!(set.first_fit(2, 3..100), Some(8..10));
+        assert_eq!(set.first_fit(1, 3..100), Some(3..4));
+        assert_eq!(set.first_fit(3, 0..10), None);
+    }
+
+    // Always the same leftmost fit as looking through every range, however the set got there
+    #[test]
+    fn test_first_fit_churn() {
+        let mut set: IntervalSet<usize> = IntervalSet::new();
+        let mut seed: usize = 12345;
+        let mut random = |n: usize| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % n
+        };
+
+        for _ in 0..2000 {
+            let start = random(500);
+            let range = start..start + random(12);
+            if random(3) == 0 {
+                set.remove(range);
+            } else {
+                set.insert(range);
+            }
+
+            let len = random(15);
+            let from = random(500);
+            let within = from..from + random(300);
+            let expected = set
+                .iter()
+                .map(|r| r.start.max(within.start)..r.end.min(within.end))
+                .find(|r| r.start < r.end && r.end - r.start >= len)
+                .map(|r| r.start..r.start + len);
+            assert_eq!(
+                set.first_fit(len, within.clone()),
+                expected,
+                "{len} in {within:?}"
+            );
+        }
+        assert_eq!(
+            set.by_len.values().map(|s| s.len()).sum::<usize>(),
+            set.ranges()
+        );
+    }
+
+    #[test]
+    fn test_split_off() {
+        let mut set: IntervalSet<usize> = [0..5, 7..9].into_iter().collect();
+        let rest = set.split_off(3);
+        assert_eq!(ranges(&set), vec![0..3]);
+        assert_eq!(ranges(&rest), vec![3..5, 7..9]);
+        assert_eq!((set.len(), rest.len()), (3, 4));
+
+        set.merge(&rest);
+        assert_eq!(ranges(&set), vec![0..5, 7..9]);
+    }
+}
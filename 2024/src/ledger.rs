@@ -0,0 +1,263 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Answer, Part};
+
+/// Where the ledger lives, relative to the package root
+pub const DEFAULT_LEDGER: &str = "answers.toml";
+
+/// Answers which have been submitted, by day and part. In the file each day is a `dayN` table
+/// with `part1` and `part2` tables under it.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Ledger {
+    days: BTreeMap<String, Day>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Day {
+    part1: Option<Record>,
+    part2: Option<Record>,
+}
+
+/// Everything we know about one part of a puzzle
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Record {
+    /// The answer the site accepted, if we've got it yet
+    pub answer: Option<Value>,
+    /// Answers the site rejected
+    #[serde(default)]
+    pub wrong: Vec<Guess>,
+}
+
+/// An answer as written in the ledger. Numbers can be written as numbers, anything else as a
+/// string.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Value {
+    Int(i64),
+    Text(String),
+}
+
+/// A rejected answer, and what the site said about it
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guess {
+    pub guess: Value,
+    pub hint: Option<Hint>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Hint {
+    Low,
+    High,
+}
+
+/// How an answer compares to what's in the ledger
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    Correct,
+    // There's an accepted answer and this isn't it
+    Incorrect { expected: Value },
+    // Exactly one of the answers that got rejected
+    KnownWrong(Guess),
+    // Not bigger than something which was already too low
+    TooLow { bound: i64 },
+    // Not smaller than something which was already too high
+    TooHigh { bound: i64 },
+    // Nothing to compare against
+    Unknown,
+}
+
+impl Ledger {
+    /// Load the ledger, or an empty one if the file doesn't exist yet
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => s.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Ledger::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&Record> {
+        let day = self.days.get(&format!("day{day}"))?;
+        match part {
+            Part::One => day.part1.as_ref(),
+            Part::Two => day.part2.as_ref(),
+        }
+    }
+
+    /// Compare an answer to what's been submitted before
+    pub fn check(&self, day: u8, part: Part, answer: &Answer) -> Check {
+        match self.get(day, part) {
+            Some(record) => record.check(answer),
+            None => Check::Unknown,
+        }
+    }
+}
+
+impl std::str::FromStr for Ledger {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl Record {
+    pub fn check(&self, answer: &Answer) -> Check {
+        let text = answer.to_string();
+        if let Some(expected) = &self.answer {
+            if expected.to_string() == text {
+                return Check::Correct;
+            }
+        }
+
+        if let Some(guess) = self.wrong.iter().find(|g| g.guess.to_string() == text) {
+            return Check::KnownWrong(guess.clone());
+        }
+
+        if let Some(n) = as_int(answer) {
+            let bound = |hint| {
+                self.wrong
+                    .iter()
+                    .filter(move |g| g.hint == Some(hint))
+                    .filter_map(|g| match g.guess {
+                        Value::Int(b) => Some(b),
+                        Value::Text(_) => None,
+                    })
+            };
+            if let Some(low) = bound(Hint::Low).max().filter(|&low| n <= low as i128) {
+                return Check::TooLow { bound: low };
+            }
+            if let Some(high) = bound(Hint::High).min().filter(|&high| n >= high as i128) {
+                return Check::TooHigh { bound: high };
+            }
+        }
+
+        match &self.answer {
+            Some(expected) => Check::Incorrect {
+                expected: expected.clone(),
+            },
+            None => Check::Unknown,
+        }
+    }
+}
+
+// Numeric answers, widened so that every u64 fits
+fn as_int(answer: &Answer) -> Option<i128> {
+    match answer {
+        Answer::Int(n) => Some(*n as i128),
+        Answer::UInt(n) => Some(*n as i128),
+        Answer::Text(_) | Answer::Unsolved => None,
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{n}"),
+            Value::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Check::Correct => write!(f, "is correct"),
+            Check::Incorrect { expected } => write!(f, "doesn't match the accepted {expected}"),
+            Check::KnownWrong(Guess { hint, .. }) => match hint {
+                Some(Hint::Low) => write!(f, "was already guessed, and it was too low"),
+                Some(Hint::High) => write!(f, "was already guessed, and it was too high"),
+                None => write!(f, "was already guessed, and it was wrong"),
+            },
+            Check::TooLow { bound } => write!(f, "is too low, since {bound} already was"),
+            Check::TooHigh { bound } => write!(f, "is too high, since {bound} already was"),
+            Check::Unknown => write!(f, "isn't in the ledger"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEDGER: &str = r#"
+[day4.part1]
+answer = 2545
+wrong = [
+    { guess = 714, hint = "low" },
+    { guess = 1479, hint = "low" },
+]
+
+[day6.part2]
+wrong = [
+    { guess = 186, hint = "low" },
+    { guess = 2000, hint = "high" },
+    { guess = 1800 },
+]
+
+[day9.part1]
+answer = "hello"
+"#;
+
+    fn ledger() -> Ledger {
+        LEDGER.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let ledger = ledger();
+        let record = ledger.get(4, Part::One).unwrap();
+        assert_eq!(record.answer, Some(Value::Int(2545)));
+        assert_eq!(record.wrong.len(), 2);
+        assert!(ledger.get(4, Part::Two).is_none());
+        assert!(ledger.get(5, Part::One).is_none());
+        assert_eq!(
+            ledger.get(9, Part::One).unwrap().answer,
+            Some(Value::Text("hello".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let ledger = ledger();
+        assert_eq!(ledger.check(4, Part::One, &2545.into()), Check::Correct);
+        assert!(matches!(
+            ledger.check(4, Part::One, &1479.into()),
+            Check::KnownWrong(Guess {
+                hint: Some(Hint::Low),
+                ..
+            })
+        ));
+        assert_eq!(
+            ledger.check(4, Part::One, &1000.into()),
+            Check::TooLow { bound: 1479 }
+        );
+        assert_eq!(
+            ledger.check(4, Part::One, &3000.into()),
+            Check::Incorrect {
+                expected: Value::Int(2545)
+            }
+        );
+
+        assert_eq!(ledger.check(6, Part::Two, &1793.into()), Check::Unknown);
+        assert_eq!(
+            ledger.check(6, Part::Two, &2500.into()),
+            Check::TooHigh { bound: 2000 }
+        );
+        assert!(matches!(
+            ledger.check(6, Part::Two, &1800.into()),
+            Check::KnownWrong(Guess { hint: None, .. })
+        ));
+
+        assert_eq!(ledger.check(9, Part::One, &"hello".into()), Check::Correct);
+        assert_eq!(ledger.check(1, Part::One, &1.into()), Check::Unknown);
+    }
+}
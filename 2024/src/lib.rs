@@ -1,8 +1,23 @@
-use indicatif::ProgressStyle;
+pub mod bench;
+pub mod collections;
+pub mod cycle;
+pub mod days;
+mod error;
+pub mod fetch;
+pub mod geom;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod ledger;
+pub mod memo;
+pub mod order;
+pub mod parallel;
+pub mod parse;
+pub mod progress;
+pub mod render;
+pub mod scaffold;
+pub mod search;
+mod solution;
 
-pub fn progress_style() -> ProgressStyle {
-    ProgressStyle::with_template(
-        "[Elapsed: {elapsed_precise} | ETA: {eta_precise}] {wide_bar} {human_pos}/{human_len} ",
-    )
-    .unwrap()
-}
+pub use error::{AocError, ParseErrorKind};
+pub use solution::{Answer, Part, Solution, Solver};
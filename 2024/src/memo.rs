@@ -0,0 +1,134 @@
+use log::info;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+/// A cache for a recursive function, keyed by its arguments. The function gets the cache handed
+/// back to it so it can recurse through it, e.g.
+///
+/// ```
+/// use aoc2024::memo::Memo;
+///
+/// fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+///     memo.get(n, |memo| if n < 2 { n } else { fib(memo, n - 1) + fib(memo, n - 2) })
+/// }
+///
+/// let mut memo = Memo::new("fib");
+/// assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+/// ```
+///
+/// Keep one around across calls to share work between them. The hit/miss counts get logged when
+/// it's dropped.
+pub struct Memo<K, V> {
+    name: &'static str,
+    cache: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+/// How well a cache did
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: usize,
+    pub misses: usize,
+    /// Number of entries in the cache
+    pub size: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lookups = self.hits + self.misses;
+        let rate = if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64 * 100.0
+        };
+        write!(
+            f,
+            "{} hits, {} misses ({rate:.1}% hit rate), {} entries",
+            self.hits, self.misses, self.size
+        )
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    /// An empty cache. The name is just for logging.
+    pub fn new(name: &'static str) -> Self {
+        Memo {
+            name,
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// The cached value for `key`, or work it out with `f` and remember it
+    pub fn get<F>(&mut self, key: K, f: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = f(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+}
+
+impl<K, V> Memo<K, V> {
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits,
+            misses: self.misses,
+            size: self.cache.len(),
+        }
+    }
+}
+
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        info!("{} cache: {}", self.name, self.stats());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Number of ways to climb `n` stairs taking 1 or 2 at a time
+    fn stairs(memo: &mut Memo<u32, u64>, n: u32) -> u64 {
+        memo.get(n, |memo| {
+            if n < 2 {
+                1
+            } else {
+                stairs(memo, n - 1) + stairs(memo, n - 2)
+            }
+        })
+    }
+
+    #[test]
+    fn test_memo() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let mut memo = Memo::new("stairs");
+        assert_eq!(stairs(&mut memo, 10), 89);
+        // Every value from 0 to 10 is worked out once, and all but the first lookup of each
+        // comes from the cache
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 8,
+                misses: 11,
+                size: 11
+            }
+        );
+
+        // Sharing the cache means later calls only do the new work
+        assert_eq!(stairs(&mut memo, 12), 233);
+        assert_eq!(memo.stats().misses, 13);
+        assert_eq!(memo.stats().size, 13);
+    }
+}
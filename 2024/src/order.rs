@@ -0,0 +1,237 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+
+/// A set of "a comes before b" rules, which don't have to say anything about every pair of items.
+/// Only the rules between the items being sorted get used, so the rules as a whole are allowed
+/// to go round in circles as long as any one sort doesn't run into one.
+#[derive(Clone, Debug)]
+pub struct PartialOrder<T> {
+    // Everything that each item must come before
+    after: HashMap<T, HashSet<T>>,
+}
+
+impl<T: Copy + Hash + Ord> Default for PartialOrder<T> {
+    fn default() -> Self {
+        PartialOrder {
+            after: HashMap::new(),
+        }
+    }
+}
+
+/// Items in an order which satisfies all the rules between them
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sorted<T> {
+    pub items: Vec<T>,
+    /// Whether this is the only order which satisfies the rules. If it isn't, ties were broken by
+    /// putting the smaller item first.
+    pub unique: bool,
+}
+
+/// Some of the rules contradict each other. Each item in the cycle must come before the next, and
+/// the last one before the first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CycleError<T> {
+    pub cycle: Vec<T>,
+}
+
+impl<T: fmt::Display> fmt::Display for CycleError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for item in &self.cycle {
+            write!(f, "{item} -> ")?;
+        }
+        match self.cycle.first() {
+            Some(first) => write!(f, "{first}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Copy + Hash + Ord> PartialOrder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule that `before` must come before `after`
+    pub fn add(&mut self, before: T, after: T) {
+        self.after.entry(before).or_default().insert(after);
+    }
+
+    /// How many rules there are
+    pub fn len(&self) -> usize {
+        self.after.values().map(|after| after.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.after.is_empty()
+    }
+
+    /// Whether there's a rule that `a` comes before `b`
+    pub fn before(&self, a: T, b: T) -> bool {
+        self.after.get(&a).is_some_and(|after| after.contains(&b))
+    }
+
+    /// Whether the items are already in an order that doesn't break any rules
+    pub fn is_sorted(&self, items: &[T]) -> bool {
+        let mut seen = HashSet::new();
+        for item in items {
+            // If we've already seen something which should have come after this, it's out of order
+            if let Some(after) = self.after.get(item) {
+                if !seen.is_disjoint(after) {
+                    return false;
+                }
+            }
+            seen.insert(*item);
+        }
+        true
+    }
+
+    /// Put the items in order with Kahn's algorithm, using only the rules between them. Nothing
+    /// gets lost, so any duplicates end up next to each other.
+    pub fn sort(&self, items: &[T]) -> Result<Sorted<T>, CycleError<T>> {
+        let graph = self.restrict(items);
+
+        let mut copies: HashMap<T, usize> = HashMap::new();
+        for &item in items {
+            *copies.entry(item).or_default() += 1;
+        }
+
+        let mut incoming: HashMap<T, usize> = items.iter().map(|&item| (item, 0)).collect();
+        for after in graph.values() {
+            for item in after {
+                *incoming.get_mut(item).unwrap() += 1;
+            }
+        }
+
+        // Items with nothing left which has to come before them. Kept sorted so ties always get
+        // broken the same way.
+        let mut ready: BTreeSet<T> = incoming
+            .iter()
+            .filter(|(_, &n)| n == 0)
+            .map(|(&item, _)| item)
+            .collect();
+        let mut sorted = Vec::with_capacity(items.len());
+        let mut unique = true;
+
+        while let Some(item) = ready.pop_first() {
+            // More than one choice of what goes next means there's more than one valid order
+            if !ready.is_empty() {
+                unique = false;
+            }
+            sorted.extend(std::iter::repeat_n(item, copies[&item]));
+            for next in &graph[&item] {
+                let n = incoming.get_mut(next).unwrap();
+                *n -= 1;
+                if *n == 0 {
+                    ready.insert(*next);
+                }
+            }
+        }
+
+        if sorted.len() < items.len() {
+            let stuck = incoming.into_iter().filter(|(_, n)| *n > 0).map(|(i, _)| i);
+            return Err(find_cycle(&graph, stuck.collect()));
+        }
+
+        Ok(Sorted {
+            items: sorted,
+            unique,
+        })
+    }
+
+    // The rules between just these items
+    fn restrict(&self, items: &[T]) -> HashMap<T, BTreeSet<T>> {
+        let wanted: HashSet<T> = items.iter().copied().collect();
+        wanted
+            .iter()
+            .map(|item| {
+                let after = self.after.get(item).into_iter().flatten();
+                let after = after.filter(|a| wanted.contains(a)).copied().collect();
+                (*item, after)
+            })
+            .collect()
+    }
+}
+
+// Everything Kahn's algorithm couldn't sort still has something unsorted which must come before
+// it, so following the rules backwards from any of them we eventually have to come back around
+fn find_cycle<T: Copy + Hash + Ord>(
+    graph: &HashMap<T, BTreeSet<T>>,
+    stuck: BTreeSet<T>,
+) -> CycleError<T> {
+    let mut path = Vec::new();
+    let mut on_path = HashMap::new();
+    let mut item = *stuck.first().expect("Something must be stuck");
+    while !on_path.contains_key(&item) {
+        on_path.insert(item, path.len());
+        path.push(item);
+        item = *stuck
+            .iter()
+            .find(|prev| graph[prev].contains(&item))
+            .expect("Stuck items always have a stuck item before them");
+    }
+
+    // We went backwards, and the smallest item goes first so the result is always the same
+    let mut cycle = path.split_off(on_path[&item]);
+    cycle.reverse();
+    let smallest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+    cycle.rotate_left(smallest);
+    CycleError { cycle }
+}
+
+impl<T: Copy + Hash + Ord> FromIterator<(T, T)> for PartialOrder<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut order = PartialOrder::new();
+        for (before, after) in iter {
+            order.add(before, after);
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let order: PartialOrder<u32> = [(1, 2), (2, 3), (1, 3)].into_iter().collect();
+        assert_eq!(order.len(), 3);
+        assert!(order.before(1, 2));
+        assert!(!order.before(2, 1));
+        assert!(order.is_sorted(&[1, 2, 3]));
+        assert!(!order.is_sorted(&[1, 3, 2]));
+
+        assert_eq!(
+            order.sort(&[3, 1, 2]),
+            Ok(Sorted {
+                items: vec![1, 2, 3],
+                unique: true
+            })
+        );
+
+        // Nothing says where 9 goes, so there's more than one answer
+        let sorted = order.sort(&[9, 3, 2]).unwrap();
+        assert_eq!(sorted.items, vec![2, 3, 9]);
+        assert!(!sorted.unique);
+
+        // Duplicates are kept, so the middle of the result is still the middle
+        assert_eq!(
+            order.sort(&[3, 1, 3, 2, 1]).unwrap().items,
+            vec![1, 1, 2, 3, 3]
+        );
+    }
+
+    #[test]
+    fn test_cycle() {
+        let order: PartialOrder<u32> = [(1, 2), (2, 3), (3, 4), (4, 2), (5, 1), (4, 0)]
+            .into_iter()
+            .collect();
+        let e = order.sort(&[5, 4, 3, 2, 1, 0]).unwrap_err();
+        assert_eq!(e.cycle, vec![2, 3, 4]);
+        assert_eq!(e.to_string(), "2 -> 3 -> 4 -> 2");
+
+        // Rules about items that aren't being sorted don't count
+        assert_eq!(order.sort(&[1, 2, 3]).unwrap().items, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,57 @@
+// Running independent bits of work on several threads, when built with the `parallel` feature.
+// Without it everything here just runs on the current thread, so solutions can use it either way.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Apply `f` to every item, spread across threads if we can. The results are in the same order as
+/// the items no matter which thread got to them first, so answers don't change from run to run.
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        items.par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        items.iter().map(f).collect()
+    }
+}
+
+/// Use `n` threads from now on, or one per CPU for 0. This has to happen before `map` gets called.
+pub fn set_threads(n: usize) -> Result<(), String> {
+    #[cfg(feature = "parallel")]
+    {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(n)
+            .build_global()
+            .map_err(|e| format!("Unable to start {n} threads: {e}"))
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        let _ = n;
+        Err("Built without the parallel feature, so only one thread gets used".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_map_keeps_order() {
+        let items: Vec<u64> = (0..1000).collect();
+        let calls = AtomicUsize::new(0);
+        let squares = map(&items, |&n| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            n * n
+        });
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+        assert_eq!(calls.load(Ordering::Relaxed), 1000);
+    }
+}
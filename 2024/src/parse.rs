@@ -0,0 +1,385 @@
+use std::num::ParseIntError;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::{AocError, ParseErrorKind};
+
+/// A piece of puzzle input which remembers the whole input it came from, so that anything which
+/// goes wrong while parsing it can be reported with a line and column. Derefs to the `&str`.
+#[derive(Clone, Copy, Debug)]
+pub struct Span<'a> {
+    whole: &'a str,
+    text: &'a str,
+}
+
+impl<'a> Span<'a> {
+    /// Start parsing a whole input
+    pub fn new(input: &'a str) -> Self {
+        Span {
+            whole: input,
+            text: input,
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// A span for some other part of the same input, like a regex capture of this one
+    pub fn of(&self, part: &'a str) -> Span<'a> {
+        Span {
+            whole: self.whole,
+            text: part,
+        }
+    }
+
+    /// An error about this span
+    pub fn error(&self, kind: ParseErrorKind) -> AocError {
+        AocError::parse(self.whole, self.text, kind)
+    }
+
+    /// An error saying what this span should have been instead
+    pub fn expected(&self, what: impl Into<String>) -> AocError {
+        self.error(ParseErrorKind::Expected(what.into()))
+    }
+
+    /// An error about the (empty) spot just after this span, for when something is missing
+    pub fn expected_after(&self, what: impl Into<String>) -> AocError {
+        self.of(&self.text[self.text.len()..]).expected(what)
+    }
+
+    /// The blank-line separated sections of this span. Runs of blank lines count as one break,
+    /// and blank lines at the start or end don't make empty sections.
+    pub fn sections(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let this = *self;
+        let mut rest = self.text;
+        std::iter::from_fn(move || {
+            // Skip any blank lines before the section
+            loop {
+                let first = rest.split_inclusive('\n').next()?;
+                if !first.trim().is_empty() {
+                    break;
+                }
+                rest = &rest[first.len()..];
+            }
+
+            let mut len = 0;
+            for line in rest.split_inclusive('\n') {
+                if line.trim().is_empty() {
+                    break;
+                }
+                len += line.len();
+            }
+            let section = rest[..len].trim_end_matches(['\r', '\n']);
+            rest = &rest[len..];
+            Some(this.of(section))
+        })
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let this = *self;
+        self.text.lines().map(move |line| this.of(line))
+    }
+
+    /// Parse each line with `f`. Errors which `f` returns without a position (see `LineError`)
+    /// get pinned to the line they came from.
+    pub fn map_lines<T, E: LineError>(
+        &self,
+        mut f: impl FnMut(Span<'a>) -> Result<T, E>,
+    ) -> Result<Vec<T>, AocError> {
+        self.lines()
+            .map(|line| f(line).map_err(|e| e.locate(&line)))
+            .collect()
+    }
+
+    pub fn split(&self, sep: &'a str) -> impl Iterator<Item = Span<'a>> + 'a {
+        let this = *self;
+        self.text.split(sep).map(move |part| this.of(part))
+    }
+
+    pub fn split_whitespace(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let this = *self;
+        self.text.split_whitespace().map(move |part| this.of(part))
+    }
+
+    /// Every integer in this span, with a `-` right before the digits making it negative.
+    /// Anything else in between is ignored.
+    pub fn ints<T: FromStr<Err = ParseIntError>>(&self) -> Result<Vec<T>, AocError> {
+        self.numbers(true)
+    }
+
+    /// Every run of digits in this span. Unlike `ints`, a `-` is just another separator.
+    pub fn uints<T: FromStr<Err = ParseIntError>>(&self) -> Result<Vec<T>, AocError> {
+        self.numbers(false)
+    }
+
+    fn numbers<T: FromStr<Err = ParseIntError>>(&self, signed: bool) -> Result<Vec<T>, AocError> {
+        let bytes = self.text.as_bytes();
+        let mut numbers = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            let negative =
+                signed && bytes[i] == b'-' && bytes.get(i + 1).is_some_and(|b| b.is_ascii_digit());
+            if !negative && !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            let number = self.of(&self.text[start..i]);
+            numbers.push(
+                number
+                    .text
+                    .parse()
+                    .map_err(|e| number.error(ParseErrorKind::InvalidNumber(e)))?,
+            );
+        }
+        Ok(numbers)
+    }
+
+    /// Each character of this span as a single digit
+    pub fn digits(&self) -> Result<Vec<u8>, AocError> {
+        self.text
+            .char_indices()
+            .map(|(i, c)| match c.to_digit(10) {
+                Some(d) => Ok(d as u8),
+                None => Err(self
+                    .of(&self.text[i..i + c.len_utf8()])
+                    .error(ParseErrorKind::UnexpectedChar(c))),
+            })
+            .collect()
+    }
+
+    /// Parse the whole span, ignoring surrounding whitespace
+    pub fn parse<T: Field<'a>>(&self) -> Result<T, AocError> {
+        T::from_span(self.of(self.text.trim()))
+    }
+
+    /// Parse `sep`-separated fields into a tuple, like `(u32, u32)` from `47|53`
+    pub fn record<R: Record<'a>>(&self, sep: &'a str) -> Result<R, AocError> {
+        let fields: Vec<Span<'a>> = self.split(sep).collect();
+        R::from_fields(self, &fields, &format!("'{sep}'"))
+    }
+
+    /// Parse whitespace-separated fields into a tuple, like `(i32, i32)` from `3   4`
+    pub fn fields<R: Record<'a>>(&self) -> Result<R, AocError> {
+        let fields: Vec<Span<'a>> = self.split_whitespace().collect();
+        R::from_fields(self, &fields, "whitespace")
+    }
+}
+
+impl Deref for Span<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.text
+    }
+}
+
+/// Something which can be parsed out of a single span, like one field of a record
+pub trait Field<'a>: Sized {
+    fn from_span(span: Span<'a>) -> Result<Self, AocError>;
+}
+
+macro_rules! int_field {
+    ($($t:ty),+) => {
+        $(
+            impl<'a> Field<'a> for $t {
+                fn from_span(span: Span<'a>) -> Result<Self, AocError> {
+                    span.text
+                        .parse()
+                        .map_err(|e| span.error(ParseErrorKind::InvalidNumber(e)))
+                }
+            }
+        )+
+    };
+}
+
+int_field!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<'a> Field<'a> for String {
+    fn from_span(span: Span<'a>) -> Result<Self, AocError> {
+        Ok(span.text.to_string())
+    }
+}
+
+impl<'a> Field<'a> for &'a str {
+    fn from_span(span: Span<'a>) -> Result<Self, AocError> {
+        Ok(span.text)
+    }
+}
+
+impl<'a> Field<'a> for Span<'a> {
+    fn from_span(span: Span<'a>) -> Result<Self, AocError> {
+        Ok(span)
+    }
+}
+
+impl<'a> Field<'a> for char {
+    fn from_span(span: Span<'a>) -> Result<Self, AocError> {
+        let mut chars = span.text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(span.expected("a single character")),
+        }
+    }
+}
+
+// A field can hold a whitespace-separated list, like the numbers after the `:` in `190: 10 19`
+impl<'a, T: Field<'a>> Field<'a> for Vec<T> {
+    fn from_span(span: Span<'a>) -> Result<Self, AocError> {
+        span.split_whitespace().map(T::from_span).collect()
+    }
+}
+
+/// A tuple of fields, parsed from a line by `Span::record` or `Span::fields`
+pub trait Record<'a>: Sized {
+    fn from_fields(span: &Span<'a>, fields: &[Span<'a>], sep: &str) -> Result<Self, AocError>;
+}
+
+macro_rules! record {
+    ($n:literal: $($t:ident $i:tt),+) => {
+        impl<'a, $($t: Field<'a>),+> Record<'a> for ($($t,)+) {
+            fn from_fields(
+                span: &Span<'a>,
+                fields: &[Span<'a>],
+                sep: &str,
+            ) -> Result<Self, AocError> {
+                if fields.len() != $n {
+                    return Err(span.expected(format!("{} fields separated by {sep}", $n)));
+                }
+                Ok(($($t::from_span(fields[$i].of(fields[$i].as_str().trim()))?,)+))
+            }
+        }
+    };
+}
+
+record!(2: A 0, B 1);
+record!(3: A 0, B 1, C 2);
+record!(4: A 0, B 1, C 2, D 3);
+record!(5: A 0, B 1, C 2, D 3, E 4);
+
+/// Errors that `Span::map_lines` can pin to the line they happened on. `AocError`s already know
+/// where they are, so they get passed through as they are.
+pub trait LineError {
+    fn locate(self, line: &Span) -> AocError;
+}
+
+impl LineError for AocError {
+    fn locate(self, _line: &Span) -> AocError {
+        self
+    }
+}
+
+impl LineError for ParseErrorKind {
+    fn locate(self, line: &Span) -> AocError {
+        line.error(self)
+    }
+}
+
+impl LineError for ParseIntError {
+    fn locate(self, line: &Span) -> AocError {
+        line.error(ParseErrorKind::InvalidNumber(self))
+    }
+}
+
+// A description of what the line should have looked like
+impl LineError for &str {
+    fn locate(self, line: &Span) -> AocError {
+        line.expected(self)
+    }
+}
+
+impl LineError for String {
+    fn locate(self, line: &Span) -> AocError {
+        line.expected(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Where an error is, as (line, column, length)
+    fn position(e: AocError) -> (usize, usize, usize) {
+        match e {
+            AocError::Parse {
+                line, column, len, ..
+            } => (line, column, len),
+            e => panic!("Expected a parse error, got {e}"),
+        }
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "\n47|53\n97|13\n\n\n75,47\r\n97,61\n\n";
+        let span = Span::new(input);
+        let sections: Vec<&str> = span.sections().map(|s| s.as_str()).collect();
+        assert_eq!(sections, vec!["47|53\n97|13", "75,47\r\n97,61"]);
+
+        // Sections are still part of the input, so errors in them know where they are
+        let second = span.sections().nth(1).unwrap();
+        let e = second.lines().nth(1).unwrap().expected("something");
+        assert_eq!(position(e), (7, 1, 5));
+
+        assert_eq!(Span::new("").sections().count(), 0);
+    }
+
+    #[test]
+    fn test_ints() {
+        let span = Span::new("x=-3, y=14..-2 and 7-1");
+        assert_eq!(span.ints::<i32>().unwrap(), vec![-3, 14, -2, 7, -1]);
+        assert_eq!(span.uints::<u32>().unwrap(), vec![3, 14, 2, 7, 1]);
+        assert!(Span::new("no numbers - here")
+            .ints::<i8>()
+            .unwrap()
+            .is_empty());
+
+        let e = Span::new("1 2\n3 300").uints::<u8>().unwrap_err();
+        assert_eq!(position(e), (2, 3, 3));
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(Span::new("2333").digits().unwrap(), vec![2, 3, 3, 3]);
+        let e = Span::new("12x4").digits().unwrap_err();
+        assert_eq!(position(e), (1, 3, 1));
+    }
+
+    #[test]
+    fn test_record() {
+        let span = Span::new("190: 10 19");
+        let (value, numbers): (u64, Vec<u64>) = span.record(":").unwrap();
+        assert_eq!((value, numbers), (190, vec![10, 19]));
+
+        let (a, b, c): (i32, char, String) = Span::new("-1 x  abc").fields().unwrap();
+        assert_eq!((a, b, c.as_str()), (-1, 'x', "abc"));
+
+        let e = Span::new("47|53|1").record::<(u32, u32)>("|").unwrap_err();
+        assert!(e.to_string().contains("2 fields separated by '|'"), "{e}");
+        let e = Span::new("47|5x").record::<(u32, u32)>("|").unwrap_err();
+        assert_eq!(position(e), (1, 4, 2));
+    }
+
+    #[test]
+    fn test_map_lines() {
+        let span = Span::new("1 2\n3 4\n5\n");
+        let pairs: Result<Vec<(u8, u8)>, _> = span.map_lines(|line| line.fields());
+        assert_eq!(position(pairs.unwrap_err()), (3, 1, 1));
+
+        // Errors without a position get pinned to the whole line
+        let lens = span.map_lines(|line| match line.len() {
+            3 => Ok(line.len()),
+            _ => Err("three characters"),
+        });
+        let e = lens.unwrap_err();
+        assert_eq!(e.to_string(), "Line 3, column 1: expected three characters");
+
+        let numbers = Span::new("12\nab\n").map_lines(|line| line.as_str().parse::<u32>());
+        assert_eq!(position(numbers.unwrap_err()), (2, 1, 2));
+    }
+}
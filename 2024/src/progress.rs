@@ -0,0 +1,169 @@
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use indicatif::{ProgressBar, ProgressStyle};
+use log::info;
+
+// Progress reporting for slow solutions. Solutions just make a Progress and tick it along, and the
+// runner decides what that looks like. Unless the runner says otherwise nothing gets shown at all,
+// so tests and benchmarks stay quiet.
+
+/// How progress gets shown
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Nothing at all
+    Hidden,
+    /// A progress bar on stderr
+    Bar,
+    /// An info log line every so often, for when stderr is going somewhere other than a person
+    Log,
+}
+
+impl Mode {
+    /// A bar if there's somebody watching stderr, otherwise log lines
+    pub fn detect() -> Mode {
+        if io::stderr().is_terminal() {
+            Mode::Bar
+        } else {
+            Mode::Log
+        }
+    }
+}
+
+static MODE: AtomicU8 = AtomicU8::new(Mode::Hidden as u8);
+
+/// How often to log in `Mode::Log`
+const LOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Set how progress is shown from now on
+pub fn set_mode(mode: Mode) {
+    MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+pub fn mode() -> Mode {
+    match MODE.load(Ordering::Relaxed) {
+        m if m == Mode::Bar as u8 => Mode::Bar,
+        m if m == Mode::Log as u8 => Mode::Log,
+        _ => Mode::Hidden,
+    }
+}
+
+fn bar_style() -> ProgressStyle {
+    ProgressStyle::with_template(
+        "[Elapsed: {elapsed_precise} | ETA: {eta_precise}] {wide_bar} {human_pos}/{human_len} ",
+    )
+    .unwrap()
+}
+
+/// Progress through `len` steps of something. It can be shared between threads.
+pub struct Progress {
+    inner: Inner,
+}
+
+enum Inner {
+    Hidden,
+    Bar(ProgressBar),
+    Log {
+        name: String,
+        len: u64,
+        pos: AtomicU64,
+        start: Instant,
+        last_logged: Mutex<Instant>,
+    },
+}
+
+impl Progress {
+    /// Start tracking progress, shown however the runner asked for. The name is for log lines.
+    pub fn new(name: &str, len: u64) -> Self {
+        let inner = match mode() {
+            Mode::Hidden => Inner::Hidden,
+            Mode::Bar => Inner::Bar(ProgressBar::new(len).with_style(bar_style())),
+            Mode::Log => Inner::Log {
+                name: name.to_string(),
+                len,
+                pos: AtomicU64::new(0),
+                start: Instant::now(),
+                last_logged: Mutex::new(Instant::now()),
+            },
+        };
+        Progress { inner }
+    }
+
+    /// Move along `n` steps
+    pub fn inc(&self, n: u64) {
+        match &self.inner {
+            Inner::Hidden => {}
+            Inner::Bar(bar) => bar.inc(n),
+            Inner::Log {
+                name,
+                len,
+                pos,
+                start,
+                last_logged,
+            } => {
+                let pos = pos.fetch_add(n, Ordering::Relaxed) + n;
+                // Don't hold things up if another thread is busy logging
+                let Ok(mut last) = last_logged.try_lock() else {
+                    return;
+                };
+                if last.elapsed() >= LOG_INTERVAL {
+                    *last = Instant::now();
+                    let percent = pos as f64 / (*len).max(1) as f64 * 100.0;
+                    info!(
+                        "{name}: {pos}/{len} ({percent:.0}%) after {:.0?}",
+                        start.elapsed()
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        match &self.inner {
+            Inner::Hidden => {}
+            Inner::Bar(bar) => bar.finish_and_clear(),
+            Inner::Log {
+                name, len, start, ..
+            } => info!("{name}: done with all {len} in {:.1?}", start.elapsed()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hidden_by_default() {
+        assert_eq!(mode(), Mode::Hidden);
+        let progress = Progress::new("test", 10);
+        assert!(matches!(progress.inner, Inner::Hidden));
+        progress.inc(3);
+    }
+
+    #[test]
+    fn test_log_counts() {
+        let progress = Progress {
+            inner: Inner::Log {
+                name: "test".to_string(),
+                len: 10,
+                pos: AtomicU64::new(0),
+                start: Instant::now(),
+                last_logged: Mutex::new(Instant::now()),
+            },
+        };
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| progress.inc(2));
+            }
+        });
+        let Inner::Log { pos, .. } = &progress.inner else {
+            panic!("Should be logging");
+        };
+        assert_eq!(pos.load(Ordering::Relaxed), 8);
+    }
+}
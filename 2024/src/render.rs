@@ -0,0 +1,233 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::geom::UPoint;
+use crate::grid::Grid;
+
+// Drawing grids of characters for debugging, as text, coloured text or images. Solutions build a
+// `Grid<char>` of whatever they want to show, and pick a colour for each character.
+
+/// A colour, as red, green and blue
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+/// How to draw a grid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Just the characters
+    Text,
+    /// The characters, coloured in with terminal escape codes
+    Ansi,
+    /// A binary PPM image, with a square of pixels for each cell
+    Ppm,
+    Png,
+}
+
+impl Format {
+    /// File extension for images
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Text | Format::Ansi => "txt",
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "ansi" => Ok(Format::Ansi),
+            "ppm" => Ok(Format::Ppm),
+            "png" => Ok(Format::Png),
+            _ => Err(format!(
+                "Unknown format {s}, expected text, ansi, ppm or png"
+            )),
+        }
+    }
+}
+
+/// Draws grids in one format, using a palette to colour in each character
+pub struct Renderer<P> {
+    pub format: Format,
+    palette: P,
+    /// Size of each cell in an image, in pixels
+    pub scale: usize,
+}
+
+impl<P: Fn(char) -> Rgb> Renderer<P> {
+    pub fn new(format: Format, palette: P) -> Self {
+        Renderer {
+            format,
+            palette,
+            scale: 4,
+        }
+    }
+
+    /// Draw the grid. Text formats come out as UTF-8.
+    pub fn render(&self, canvas: &Grid<char>) -> Vec<u8> {
+        match self.format {
+            Format::Text => canvas.to_string().into_bytes(),
+            Format::Ansi => self.ansi(canvas).into_bytes(),
+            Format::Ppm => {
+                let (width, height, pixels) = self.pixels(canvas);
+                let mut ppm = format!("P6\n{width} {height}\n255\n").into_bytes();
+                ppm.extend(pixels);
+                ppm
+            }
+            Format::Png => {
+                let (width, height, pixels) = self.pixels(canvas);
+                let mut png = Vec::new();
+                let mut encoder = png::Encoder::new(&mut png, width as u32, height as u32);
+                encoder.set_color(png::ColorType::Rgb);
+                encoder.set_depth(png::BitDepth::Eight);
+                // Writing to memory can't fail
+                let mut writer = encoder.write_header().unwrap();
+                writer.write_image_data(&pixels).unwrap();
+                writer.finish().unwrap();
+                png
+            }
+        }
+    }
+
+    fn ansi(&self, canvas: &Grid<char>) -> String {
+        let mut s = String::new();
+        for y in 0..canvas.height() {
+            let mut last = None;
+            for &c in canvas.row(y) {
+                // Only change colour when we have to, which keeps the output a lot smaller
+                let colour = (self.palette)(c);
+                if last != Some(colour) {
+                    let Rgb(r, g, b) = colour;
+                    let _ = write!(s, "\x1b[38;2;{r};{g};{b}m");
+                    last = Some(colour);
+                }
+                s.push(c);
+            }
+            s.push_str("\x1b[0m\n");
+        }
+        s
+    }
+
+    // Width, height and RGB bytes of the grid drawn as an image
+    fn pixels(&self, canvas: &Grid<char>) -> (usize, usize, Vec<u8>) {
+        let width = canvas.width() * self.scale;
+        let height = canvas.height() * self.scale;
+        let mut pixels = Vec::with_capacity(width * height * 3);
+        for y in 0..height {
+            for x in 0..width {
+                let c = canvas[UPoint::new(x / self.scale, y / self.scale)];
+                let Rgb(r, g, b) = (self.palette)(c);
+                pixels.extend([r, g, b]);
+            }
+        }
+        (width, height, pixels)
+    }
+}
+
+/// Numbered image files in a directory, which something like ffmpeg can turn into an animation
+pub struct Frames<P> {
+    renderer: Renderer<P>,
+    dir: PathBuf,
+    count: usize,
+}
+
+impl<P: Fn(char) -> Rgb> Frames<P> {
+    pub fn new(renderer: Renderer<P>, dir: &Path) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        Ok(Frames {
+            renderer,
+            dir: dir.to_path_buf(),
+            count: 0,
+        })
+    }
+
+    /// Save the next frame, returning where it went
+    pub fn add(&mut self, canvas: &Grid<char>) -> io::Result<PathBuf> {
+        let name = format!(
+            "frame{:05}.{}",
+            self.count,
+            self.renderer.format.extension()
+        );
+        let path = self.dir.join(name);
+        fs::write(&path, self.renderer.render(canvas))?;
+        self.count += 1;
+        Ok(path)
+    }
+
+    /// How many frames have been saved
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette(c: char) -> Rgb {
+        match c {
+            '#' => Rgb(255, 255, 255),
+            _ => Rgb(0, 0, 0),
+        }
+    }
+
+    fn canvas() -> Grid<char> {
+        Grid::parse("#.\n..\n", Some).unwrap()
+    }
+
+    #[test]
+    fn test_text() {
+        let renderer = Renderer::new(Format::Text, palette);
+        assert_eq!(renderer.render(&canvas()), b"#.\n..\n");
+
+        let ansi = Renderer::new(Format::Ansi, palette).render(&canvas());
+        let ansi = String::from_utf8(ansi).unwrap();
+        assert!(ansi.starts_with("\x1b[38;2;255;255;255m#\x1b[38;2;0;0;0m.\x1b[0m\n"));
+    }
+
+    #[test]
+    fn test_images() {
+        let mut renderer = Renderer::new(Format::Ppm, palette);
+        renderer.scale = 2;
+        let ppm = renderer.render(&canvas());
+        let header = b"P6\n4 4\n255\n";
+        assert!(ppm.starts_with(header));
+        assert_eq!(ppm.len(), header.len() + 4 * 4 * 3);
+        // The top left 2x2 pixels are the obstruction
+        assert_eq!(
+            ppm[header.len()..header.len() + 9],
+            [255, 255, 255, 255, 255, 255, 0, 0, 0]
+        );
+
+        let png = Renderer::new(Format::Png, palette).render(&canvas());
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+
+    #[test]
+    fn test_frames() {
+        let dir = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let mut frames = Frames::new(Renderer::new(Format::Ppm, palette), &dir).unwrap();
+        frames.add(&canvas()).unwrap();
+        let path = frames.add(&canvas()).unwrap();
+        assert_eq!(path, dir.join("frame00001.ppm"));
+        assert_eq!(frames.len(), 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!("png".parse(), Ok(Format::Png));
+        assert!("gif".parse::<Format>().is_err());
+    }
+}
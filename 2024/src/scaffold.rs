@@ -0,0 +1,190 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+
+/// Generate the source for a new day from the template
+pub fn day_source(day: u8) -> String {
+    TEMPLATE.replace("{{day}}", &day.to_string())
+}
+
+/// Add a day to the module declarations and the `DAYS` registry in the given days/mod.rs source.
+/// Returns None if the file doesn't look like we expect, or if the day is already in there.
+pub fn register(mod_rs: &str, day: u8) -> Option<String> {
+    let module = format!("pub mod day{day};");
+    let entry = format!("    ({day}, &day{day}::Day{day}),");
+    if mod_rs.lines().any(|line| line == module || line == entry) {
+        return None;
+    }
+
+    let mut lines: Vec<String> = mod_rs.lines().map(str::to_string).collect();
+
+    // Module declarations are kept in the order rustfmt likes, which is alphabetical
+    let first_mod = lines.iter().position(|l| l.starts_with("pub mod day"))?;
+    let mods = lines[first_mod..]
+        .iter()
+        .take_while(|l| l.starts_with("pub mod day"))
+        .count();
+    let mut block: Vec<String> = lines.drain(first_mod..first_mod + mods).collect();
+    block.push(module);
+    block.sort_by(|a, b| a.trim_end_matches(';').cmp(b.trim_end_matches(';')));
+    lines.splice(first_mod..first_mod, block);
+
+    // Registry entries are in order of day
+    let start = lines.iter().position(|l| l.starts_with("pub const DAYS"))?;
+    let end = start + lines[start..].iter().position(|l| l == "];")?;
+    let at = (start + 1..end)
+        .find(|&i| entry_day(&lines[i]).is_some_and(|d| d > day))
+        .unwrap_or(end);
+    lines.insert(at, entry);
+
+    Some(lines.join("\n") + "\n")
+}
+
+// The day number of a `(N, &dayN::DayN),` registry line
+fn entry_day(line: &str) -> Option<u8> {
+    line.trim()
+        .strip_prefix('(')?
+        .split(',')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Set up a new day under `root`, which is the package directory: a source file from the template,
+/// registered with the runner, plus empty puzzle and example inputs if they aren't there already.
+/// Refuses to touch a day which already exists. Returns the files which were created.
+pub fn new_day(root: &Path, day: u8) -> io::Result<Vec<PathBuf>> {
+    let source = root.join("src/days").join(format!("day{day}.rs"));
+    if source.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", source.display()),
+        ));
+    }
+
+    let mod_path = root.join("src/days/mod.rs");
+    let mod_rs = fs::read_to_string(&mod_path)?;
+    let registered = register(&mod_rs, day).ok_or_else(|| {
+        io::Error::other(format!(
+            "Unable to add day {day} to {}, is it already there?",
+            mod_path.display()
+        ))
+    })?;
+
+    // Everything's been checked, but writing can still fail part way. If it does, take back what
+    // got written so that trying again doesn't find half a day there already. Registering goes
+    // last since that's the one thing which can't just be deleted.
+    let mut created = Vec::new();
+    let result = write_files(root, day, &source, &mut created)
+        .and_then(|()| fs::write(&mod_path, registered));
+    if let Err(e) = result {
+        for path in &created {
+            let _ = fs::remove_file(path);
+        }
+        return Err(e);
+    }
+
+    Ok(created)
+}
+
+// Write the new day's source and any missing inputs, noting each file as it's created
+fn write_files(root: &Path, day: u8, source: &Path, created: &mut Vec<PathBuf>) -> io::Result<()> {
+    fs::write(source, day_source(day))?;
+    created.push(source.to_path_buf());
+
+    for name in [format!("day{day}.txt"), format!("day{day}_test.txt")] {
+        let path = root.join("data").join(name);
+        if !path.exists() {
+            fs::write(&path, "")?;
+            created.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD_RS: &str = "use crate::Solver;
+
+pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod day9;
+
+pub const DAYS: &[(u8, &dyn Solver)] = &[
+    (1, &day1::Day1),
+    (2, &day2::Day2),
+    (9, &day9::Day9),
+    (10, &day10::Day10),
+];
+";
+
+    #[test]
+    fn test_register() {
+        let registered = register(MOD_RS, 3).unwrap();
+        assert!(registered.contains("pub mod day2;\npub mod day3;\npub mod day9;"));
+        assert!(registered.contains("(2, &day2::Day2),\n    (3, &day3::Day3),\n    (9,"));
+
+        let registered = register(MOD_RS, 11).unwrap();
+        assert!(registered.contains("pub mod day10;\npub mod day11;\npub mod day2;"));
+        assert!(registered.contains("(10, &day10::Day10),\n    (11, &day11::Day11),\n];"));
+
+        assert_eq!(register(MOD_RS, 9), None);
+        assert_eq!(register("pub mod day1;\n", 2), None);
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src/days")).unwrap();
+        fs::create_dir_all(root.join("data")).unwrap();
+        fs::write(root.join("src/days/mod.rs"), MOD_RS).unwrap();
+        fs::write(root.join("data/day3.txt"), "already downloaded").unwrap();
+
+        let created = new_day(&root, 3).unwrap();
+        assert_eq!(created.len(), 2);
+        let source = fs::read_to_string(root.join("src/days/day3.rs")).unwrap();
+        assert!(source.contains("impl Solution for Day3 {"));
+        assert!(!source.contains("{{day}}"));
+        assert_eq!(
+            fs::read_to_string(root.join("data/day3.txt")).unwrap(),
+            "already downloaded"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("data/day3_test.txt")).unwrap(),
+            ""
+        );
+
+        let e = new_day(&root, 3).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::AlreadyExists);
+        let e = new_day(&root, 9).unwrap_err();
+        assert!(e.to_string().contains("day 9"), "{e}");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_new_day_failure() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-fail-{}", std::process::id()));
+        fs::create_dir_all(root.join("src/days")).unwrap();
+        fs::write(root.join("src/days/mod.rs"), MOD_RS).unwrap();
+
+        // No data directory to put the inputs in, so nothing should be left behind
+        assert!(new_day(&root, 3).is_err());
+        assert!(!root.join("src/days/day3.rs").exists());
+        assert_eq!(
+            fs::read_to_string(root.join("src/days/mod.rs")).unwrap(),
+            MOD_RS
+        );
+
+        // Which means it works once the problem's fixed
+        fs::create_dir_all(root.join("data")).unwrap();
+        assert_eq!(new_day(&root, 3).unwrap().len(), 3);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,389 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+// Graph searches over implicit graphs, where the edges out of a node come from calling a
+// `neighbours` closure on it. Nodes just need to be cheap enough to clone and hashable, so grid
+// positions, (position, direction) pairs and the like all work.
+
+/// The result of a search: how far away every reached node is, and how we got there
+#[derive(Clone, Debug)]
+pub struct Paths<N, C> {
+    dist: HashMap<N, C>,
+    prev: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Paths<N, C> {
+    /// Distance from the nearest start, or None if it wasn't reached
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.dist.get(node).copied()
+    }
+
+    /// The node we came from on the way to `node`. None for the starts and unreached nodes.
+    pub fn previous(&self, node: &N) -> Option<&N> {
+        self.prev.get(node)
+    }
+
+    /// A shortest path from a start to `node`, including both ends
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.dist.contains_key(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        while let Some(prev) = self.prev.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every node that was reached, including the starts
+    pub fn reached(&self) -> impl Iterator<Item = &N> {
+        self.dist.keys()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.dist.contains_key(node)
+    }
+}
+
+/// Breadth-first search from some starting nodes, where every edge costs 1
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths {
+        dist: HashMap::new(),
+        prev: HashMap::new(),
+    };
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if paths.dist.insert(start.clone(), 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let d = paths.dist[&node] + 1;
+        for next in neighbours(&node) {
+            if !paths.dist.contains_key(&next) {
+                paths.dist.insert(next.clone(), d);
+                paths.prev.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    paths
+}
+
+/// Depth-first search from a node, returning every node reached in the order they were visited
+pub fn dfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+        // Push in reverse so that neighbours get visited in the order they were given
+        let next: Vec<N> = neighbours(&node)
+            .into_iter()
+            .filter(|n| !seen.contains(n))
+            .collect();
+        stack.extend(next.into_iter().rev());
+        order.push(node);
+    }
+    order
+}
+
+/// Every node reachable from a start, including the start itself. This is a flood fill when the
+/// neighbours are the adjacent cells which match.
+pub fn reachable<N, I>(start: N, neighbours: impl FnMut(&N) -> I) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    dfs(start, neighbours).into_iter().collect()
+}
+
+// Priority queue entry which only compares by cost, backwards so that BinaryHeap pops the
+// cheapest one first
+struct Entry<N, C> {
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+/// Dijkstra's algorithm from some starting nodes. `neighbours` gives each neighbour along with the
+/// cost of getting there, which can't be negative.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths {
+        dist: HashMap::new(),
+        prev: HashMap::new(),
+    };
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        paths.dist.insert(start.clone(), C::default());
+        heap.push(Entry {
+            cost: C::default(),
+            node: start,
+        });
+    }
+
+    while let Some(Entry { cost, node }) = heap.pop() {
+        if paths.dist.get(&node).is_some_and(|&best| cost > best) {
+            continue; // Already found a cheaper way here
+        }
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if paths.dist.get(&next).is_none_or(|&best| next_cost < best) {
+                paths.dist.insert(next.clone(), next_cost);
+                paths.prev.insert(next.clone(), node.clone());
+                heap.push(Entry {
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+    paths
+}
+
+/// A* search for the cheapest path from `start` to any node where `is_goal` is true. The
+/// heuristic has to never overestimate the remaining cost. Returns the cost and the path,
+/// including both ends.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    heuristic: impl Fn(&N) -> C,
+    is_goal: impl Fn(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths {
+        dist: HashMap::from([(start.clone(), C::default())]),
+        prev: HashMap::new(),
+    };
+    let mut heap = BinaryHeap::from([Entry {
+        cost: heuristic(&start),
+        node: start,
+    }]);
+
+    while let Some(Entry { node, .. }) = heap.pop() {
+        let cost = paths.dist[&node];
+        if is_goal(&node) {
+            return Some((cost, paths.path_to(&node).unwrap()));
+        }
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if paths.dist.get(&next).is_none_or(|&best| next_cost < best) {
+                paths.dist.insert(next.clone(), next_cost);
+                paths.prev.insert(next.clone(), node.clone());
+                heap.push(Entry {
+                    cost: next_cost + heuristic(&next),
+                    node: next,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// How many different paths lead from `start` to goal nodes, in a graph with no cycles. Paths can
+/// carry on through a goal to reach another one, and each counts separately.
+pub fn count_paths<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    is_goal: impl Fn(&N) -> bool,
+) -> usize
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    // Work out the count for every node reachable from the start, after the counts for all of
+    // its neighbours. Each node goes on the stack once to expand it, and once more to add up.
+    let mut counts: HashMap<N, usize> = HashMap::new();
+    let mut edges: HashMap<N, Vec<N>> = HashMap::new();
+    let mut stack = vec![start.clone()];
+    while let Some(node) = stack.pop() {
+        if counts.contains_key(&node) {
+            continue;
+        }
+        match edges.get(&node) {
+            Some(next) => {
+                let count =
+                    usize::from(is_goal(&node)) + next.iter().map(|n| counts[n]).sum::<usize>();
+                counts.insert(node, count);
+            }
+            None => {
+                let next: Vec<N> = neighbours(&node).into_iter().collect();
+                stack.push(node.clone());
+                stack.extend(next.iter().filter(|n| !counts.contains_key(n)).cloned());
+                edges.insert(node, next);
+            }
+        }
+    }
+    counts[&start]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::UPoint;
+    use crate::grid::Grid;
+
+    // S is the start, E is the end, # are walls
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E
+";
+
+    fn maze() -> Grid<char> {
+        Grid::parse(MAZE, Some).unwrap()
+    }
+
+    fn open(grid: &Grid<char>, pos: UPoint) -> impl Iterator<Item = UPoint> + '_ {
+        grid.neighbours4(pos)
+            .filter(|(_, &c)| c != '#')
+            .map(|(p, _)| p)
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = maze();
+        let start = grid.find(|&c| c == 'S').unwrap();
+        let end = grid.find(|&c| c == 'E').unwrap();
+        let paths = bfs([start], |&p| open(&grid, p));
+
+        assert_eq!(paths.distance(&start), Some(0));
+        assert_eq!(paths.distance(&end), Some(15));
+        let path = paths.path_to(&end).unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!(path[0], start);
+        assert!(path.windows(2).all(|w| w[0].is_adjacent(&w[1])));
+        assert_eq!(paths.previous(&start), None);
+
+        // Walls never get reached
+        assert_eq!(paths.distance(&UPoint::new(3, 0)), None);
+        assert_eq!(paths.path_to(&UPoint::new(3, 0)), None);
+        let open_cells = grid.positions(|&c| c != '#').count();
+        assert_eq!(paths.reached().count(), open_cells);
+    }
+
+    #[test]
+    fn test_dfs() {
+        let grid = maze();
+        let start = grid.find(|&c| c == 'S').unwrap();
+        let order = dfs(start, |&p| open(&grid, p));
+        assert_eq!(order[0], start);
+        assert_eq!(order.len(), grid.positions(|&c| c != '#').count());
+
+        // Flood fill the walls connected to the one at 1,1
+        let walls = reachable(UPoint::new(1, 1), |&p| {
+            grid.neighbours4(p)
+                .filter(|(_, &c)| c == '#')
+                .map(|(q, _)| q)
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(walls.len(), 6);
+        assert!(walls.contains(&UPoint::new(4, 3)));
+        assert!(!walls.contains(&UPoint::new(3, 0)));
+    }
+
+    // Small weighted graph where the direct route isn't the cheapest
+    fn edges(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 7), ('c', 2)],
+            'b' => vec![('d', 1)],
+            'c' => vec![('b', 3), ('d', 8)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let paths = dijkstra(['a'], edges);
+        assert_eq!(paths.distance(&'b'), Some(5));
+        assert_eq!(paths.distance(&'d'), Some(6));
+        assert_eq!(paths.path_to(&'d'), Some(vec!['a', 'c', 'b', 'd']));
+        assert_eq!(paths.distance(&'z'), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        assert_eq!(
+            astar('a', edges, |_| 0, |&n| n == 'd'),
+            Some((6, vec!['a', 'c', 'b', 'd']))
+        );
+        assert_eq!(astar('b', edges, |_| 0, |&n| n == 'a'), None);
+
+        let grid = maze();
+        let start = grid.find(|&c| c == 'S').unwrap();
+        let end = grid.find(|&c| c == 'E').unwrap();
+        let (cost, path) = astar(
+            start,
+            |&p| open(&grid, p).map(|q| (q, 1)),
+            |p| p.manhattan(&end),
+            |&p| p == end,
+        )
+        .unwrap();
+        assert_eq!(cost, 15);
+        assert_eq!(path.last(), Some(&end));
+    }
+
+    #[test]
+    fn test_count_paths() {
+        // A ladder of diamonds doubles the number of paths at each rung
+        let next = |&n: &u32| match n {
+            n if n >= 6 => vec![],
+            n if n % 2 == 0 => vec![n + 1, n + 2],
+            n => vec![n + 1],
+        };
+        assert_eq!(count_paths(0, next, |&n| n == 6), 8);
+        // Ending at any even node counts the paths which stop partway too
+        assert_eq!(count_paths(0, next, |&n| n % 2 == 0), 15);
+        assert_eq!(count_paths(0, next, |_| false), 0);
+    }
+}
@@ -0,0 +1,176 @@
+use std::any::Any;
+use std::fmt;
+
+use crate::AocError;
+
+/// Which half of a day's puzzle to run
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = String;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        match n {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            n => Err(format!("Invalid part: {n}")),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// The answer to one part of a puzzle. Solutions return whatever number type is natural for them
+/// and it gets converted into one of these.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Text(String),
+    // I haven't figured this part out yet
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::UInt(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Unsolved => write!(f, "(unsolved)"),
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident, $target:ty, $($t:ty),+) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(n as $target)
+                }
+            }
+        )+
+    };
+}
+
+answer_from!(Int, i64, i32, i64);
+answer_from!(UInt, u64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// A day's puzzle solution. The input gets parsed once and then handed to each part.
+pub trait Solution {
+    type Input: 'static;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError>;
+    fn part1(&self, input: &Self::Input) -> Answer;
+    fn part2(&self, input: &Self::Input) -> Answer;
+}
+
+/// Type-erased version of `Solution`, so that every day can live in the same registry
+pub trait Solver {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, AocError>;
+
+    // Panics if `input` didn't come from this solver's `parse`
+    fn solve(&self, input: &dyn Any, part: Part) -> Answer;
+
+    // Parse the input and run one part
+    fn run(&self, input: &str, part: Part) -> Result<Answer, AocError> {
+        let parsed = self.parse(input)?;
+        Ok(self.solve(parsed.as_ref(), part))
+    }
+}
+
+impl<S: Solution> Solver for S {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, AocError> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+
+    fn solve(&self, input: &dyn Any, part: Part) -> Answer {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("Input was parsed by a different solution");
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Doubler;
+
+    impl Solution for Doubler {
+        type Input = Vec<u32>;
+
+        fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+            input
+                .split_whitespace()
+                .map(|n| n.parse().map_err(|e| AocError::number(input, n, e)))
+                .collect()
+        }
+
+        fn part1(&self, input: &Self::Input) -> Answer {
+            input.iter().map(|n| n * 2).sum::<u32>().into()
+        }
+
+        fn part2(&self, _input: &Self::Input) -> Answer {
+            Answer::Unsolved
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let solver: &dyn Solver = &Doubler;
+        assert_eq!(solver.run("1 2 3", Part::One).unwrap(), Answer::UInt(12));
+        assert_eq!(solver.run("1 2 3", Part::Two).unwrap(), Answer::Unsolved);
+        assert!(solver.run("1 two 3", Part::One).is_err());
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(-3).to_string(), "-3");
+        assert_eq!(Answer::from(42_usize).to_string(), "42");
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+    }
+
+    #[test]
+    fn test_part_from_number() {
+        assert_eq!(Part::try_from(1), Ok(Part::One));
+        assert_eq!(Part::try_from(2), Ok(Part::Two));
+        assert!(Part::try_from(3).is_err());
+    }
+}
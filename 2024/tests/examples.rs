@@ -0,0 +1,20 @@
+// Every example input with a `.expected` sidecar in data/ gets a test for each part listed in it.
+// See build.rs for where they come from.
+
+use std::fs;
+use std::path::Path;
+
+use aoc2024::{days, Part};
+
+fn check(day: u8, file: &str, part: Part, expected: &str) {
+    let solver = days::get(day).unwrap_or_else(|| panic!("Day {day} isn't registered"));
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join(file);
+    let input = fs::read_to_string(&path).unwrap();
+
+    let answer = solver.run(&input, part).unwrap_or_else(|e| panic!("{e:#}"));
+    assert_eq!(answer.to_string(), expected, "{file} part {part}");
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));